@@ -1,4 +1,7 @@
 use crate::bdt::{Attribute, AttributeContext, Bdt, BifurcationFunction};
+use crate::static_constraints::{
+    mk_non_activation, mk_non_inhibition, mk_observability, mk_update_function_is_true,
+};
 use crate::util::functional::Functional;
 use biodivine_lib_bdd::Bdd;
 use biodivine_lib_param_bn::biodivine_std::traits::Set;
@@ -88,27 +91,10 @@ fn attributes_for_missing_constraints(
 ) {
     let context = graph.symbolic_context();
     for reg in network.as_graph().regulations() {
-        // This is straight up copied from static constraint analysis in lib-param-bn.
-        // For more context, go there.
-        let target = reg.get_target();
-        let update_function = network.get_update_function(target);
-        let fn_is_true = if let Some(function) = update_function {
-            context.mk_fn_update_true(function)
-        } else {
-            context.mk_implicit_function_is_true(target, &network.regulators(target))
-        };
-        let fn_is_false = fn_is_true.not();
-        let regulator: usize = reg.get_regulator().into();
-        let regulator = context.state_variables()[regulator];
-        let regulator_is_true = context.mk_state_variable_is_true(reg.get_regulator());
-        let regulator_is_false = context.mk_state_variable_is_true(reg.get_regulator()).not();
+        let fn_is_true = mk_update_function_is_true(network, context, reg.get_target());
 
         if !reg.is_observable() {
-            let fn_x1_to_1 = fn_is_true.and(&regulator_is_true).var_exists(regulator);
-            let fn_x0_to_1 = fn_is_true.and(&regulator_is_false).var_exists(regulator);
-            let observability = fn_x1_to_1
-                .xor(&fn_x0_to_1)
-                .exists(context.state_variables());
+            let observability = mk_observability(context, &fn_is_true, reg.get_regulator());
 
             out.push(Attribute {
                 name: format!(
@@ -127,17 +113,8 @@ fn attributes_for_missing_constraints(
         }
 
         if reg.get_monotonicity().is_none() {
-            let fn_x1_to_0 = fn_is_false.and(&regulator_is_true).var_exists(regulator);
-            let fn_x0_to_1 = fn_is_true.and(&regulator_is_false).var_exists(regulator);
-            let non_activation = fn_x0_to_1
-                .and(&fn_x1_to_0)
-                .exists(context.state_variables());
-
-            let fn_x0_to_0 = fn_is_false.and(&regulator_is_false).var_exists(regulator);
-            let fn_x1_to_1 = fn_is_true.and(&regulator_is_true).var_exists(regulator);
-            let non_inhibition = fn_x0_to_0
-                .and(&fn_x1_to_1)
-                .exists(context.state_variables());
+            let non_activation = mk_non_activation(context, &fn_is_true, reg.get_regulator());
+            let non_inhibition = mk_non_inhibition(context, &fn_is_true, reg.get_regulator());
 
             out.push(Attribute {
                 name: format!(
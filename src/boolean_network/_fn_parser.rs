@@ -0,0 +1,234 @@
+use crate::boolean_network::_fn_tokenizer::{tokenize_update_function, FnToken};
+use serde::{Deserialize, Serialize};
+
+/// The reason why an update function was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum FnErrorReason {
+    Syntax,
+    UnknownVariable,
+    MissingRegulation,
+    ArityMismatch,
+}
+
+/// A structured error describing why an update function is invalid.
+///
+/// The `offset` is a character (not byte) offset into the function string and `token` is
+/// the text of the offending token (empty when the error is at the end of the input).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct UpdateFunctionError {
+    pub offset: usize,
+    pub token: String,
+    pub reason: FnErrorReason,
+    pub message: String,
+}
+
+/// The symbols referenced by a syntactically valid update function. Each symbol is paired
+/// with the character offset at which it appears.
+///
+/// Note that a bare name can be either a variable or a zero-arity parameter, which can
+/// only be decided once the variables of the network are known.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FnSymbols {
+    /// Names used as standalone terms of the expression.
+    pub names: Vec<(usize, String)>,
+    /// Names used as arguments of a parameter.
+    pub arguments: Vec<(usize, String)>,
+    /// Parameter names together with the number of arguments.
+    pub parameters: Vec<(usize, String, usize)>,
+}
+
+/// Check that the given update function is syntactically valid and collect all symbols
+/// that appear in it. An empty string is a valid (implicit) update function.
+pub(crate) fn parse_update_function(function: &str) -> Result<FnSymbols, UpdateFunctionError> {
+    let tokens = tokenize_update_function(function);
+    let mut parser = FnParser {
+        tokens: &tokens,
+        position: 0,
+        end: function.chars().count(),
+        symbols: FnSymbols::default(),
+    };
+    if !tokens.is_empty() {
+        parser.parse_iff()?;
+        if parser.peek().is_some() {
+            return Err(parser.error_here("Unexpected token."));
+        }
+    }
+    Ok(parser.symbols)
+}
+
+/// **(internal)** A recursive descent parser over a list of update function tokens.
+///
+/// The operator priority is the same as in the `.aeon` format, from lowest to highest:
+/// `<=>`, `=>`, `|`, `&`, `^`, `!`.
+struct FnParser<'a> {
+    tokens: &'a [(usize, FnToken)],
+    position: usize,
+    end: usize,
+    symbols: FnSymbols,
+}
+
+impl FnParser<'_> {
+    fn peek(&self) -> Option<&FnToken> {
+        self.tokens.get(self.position).map(|(_, t)| t)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map(|(o, _)| *o)
+            .unwrap_or(self.end)
+    }
+
+    fn error_here(&self, message: &str) -> UpdateFunctionError {
+        let token = self.peek().map(|t| t.to_string()).unwrap_or_default();
+        let message = if token.is_empty() {
+            format!("{} (at the end of the function)", message)
+        } else {
+            format!("{} (found `{}`)", message, token)
+        };
+        UpdateFunctionError {
+            offset: self.offset(),
+            token,
+            reason: FnErrorReason::Syntax,
+            message,
+        }
+    }
+
+    fn expect(&mut self, token: FnToken, message: &str) -> Result<(), UpdateFunctionError> {
+        if self.peek() == Some(&token) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error_here(message))
+        }
+    }
+
+    /// Parse a sequence of `operand` terms separated by the binary `operator`.
+    fn parse_binary(
+        &mut self,
+        operator: FnToken,
+        operand: fn(&mut Self) -> Result<(), UpdateFunctionError>,
+    ) -> Result<(), UpdateFunctionError> {
+        operand(self)?;
+        while self.peek() == Some(&operator) {
+            self.position += 1;
+            operand(self)?;
+        }
+        Ok(())
+    }
+
+    fn parse_iff(&mut self) -> Result<(), UpdateFunctionError> {
+        self.parse_binary(FnToken::Iff, Self::parse_imp)
+    }
+
+    fn parse_imp(&mut self) -> Result<(), UpdateFunctionError> {
+        self.parse_binary(FnToken::Imp, Self::parse_or)
+    }
+
+    fn parse_or(&mut self) -> Result<(), UpdateFunctionError> {
+        self.parse_binary(FnToken::Or, Self::parse_and)
+    }
+
+    fn parse_and(&mut self) -> Result<(), UpdateFunctionError> {
+        self.parse_binary(FnToken::And, Self::parse_xor)
+    }
+
+    fn parse_xor(&mut self) -> Result<(), UpdateFunctionError> {
+        self.parse_binary(FnToken::Xor, Self::parse_unary)
+    }
+
+    fn parse_unary(&mut self) -> Result<(), UpdateFunctionError> {
+        match self.peek() {
+            Some(FnToken::Not) => {
+                self.position += 1;
+                self.parse_unary()
+            }
+            Some(FnToken::Open) => {
+                self.position += 1;
+                self.parse_iff()?;
+                self.expect(FnToken::Close, "Expected `)`.")
+            }
+            Some(FnToken::Name(name)) => {
+                let name = name.clone();
+                let offset = self.offset();
+                self.position += 1;
+                if self.peek() == Some(&FnToken::Open) {
+                    self.position += 1;
+                    let arity = self.parse_arguments()?;
+                    self.symbols.parameters.push((offset, name, arity));
+                } else if name != "true" && name != "false" {
+                    self.symbols.names.push((offset, name));
+                }
+                Ok(())
+            }
+            _ => Err(self.error_here("Expected a variable, parameter, constant or `(`.")),
+        }
+    }
+
+    /// Parse the comma separated arguments of a parameter (including the closing
+    /// parenthesis) and return their count.
+    fn parse_arguments(&mut self) -> Result<usize, UpdateFunctionError> {
+        let mut arity = 0;
+        if self.peek() == Some(&FnToken::Close) {
+            self.position += 1;
+            return Ok(arity);
+        }
+        loop {
+            match self.peek() {
+                Some(FnToken::Name(name)) => {
+                    self.symbols.arguments.push((self.offset(), name.clone()));
+                    self.position += 1;
+                    arity += 1;
+                }
+                _ => return Err(self.error_here("Expected a variable as parameter argument.")),
+            }
+            match self.peek() {
+                Some(FnToken::Comma) => self.position += 1,
+                Some(FnToken::Close) => {
+                    self.position += 1;
+                    return Ok(arity);
+                }
+                _ => return Err(self.error_here("Expected `,` or `)`.")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boolean_network::_fn_parser::{parse_update_function, FnErrorReason};
+
+    #[test]
+    fn parse_valid_functions() {
+        let symbols = parse_update_function("a & !(b | true) => f(a, c) <=> p ^ g()").unwrap();
+        let names: Vec<_> = symbols.names.iter().map(|(_, n)| n.as_str()).collect();
+        assert_eq!(vec!["a", "b", "p"], names);
+        let arguments: Vec<_> = symbols
+            .arguments
+            .iter()
+            .map(|(o, n)| (*o, n.as_str()))
+            .collect();
+        assert_eq!(vec![(21, "a"), (24, "c")], arguments);
+        let parameters: Vec<_> = symbols
+            .parameters
+            .iter()
+            .map(|(_, n, a)| (n.as_str(), *a))
+            .collect();
+        assert_eq!(vec![("f", 2), ("g", 0)], parameters);
+        assert!(parse_update_function("").is_ok());
+    }
+
+    #[test]
+    fn parse_invalid_functions() {
+        let error = parse_update_function("a & (b | c").unwrap_err();
+        assert_eq!((10, ""), (error.offset, error.token.as_str()));
+        let error = parse_update_function("a && b").unwrap_err();
+        assert_eq!((3, "&"), (error.offset, error.token.as_str()));
+        let error = parse_update_function("a = b").unwrap_err();
+        assert_eq!((2, "="), (error.offset, error.token.as_str()));
+        let error = parse_update_function("f(a & b)").unwrap_err();
+        assert_eq!((4, "&"), (error.offset, error.token.as_str()));
+        assert_eq!(FnErrorReason::Syntax, error.reason);
+    }
+}
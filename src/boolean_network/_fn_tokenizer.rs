@@ -0,0 +1,106 @@
+use std::fmt::{Display, Formatter};
+
+/// A single token of an update function expression, as written in the `.aeon` format.
+///
+/// The tokenizer never fails. Characters which cannot start any valid token are
+/// preserved as `Invalid` tokens, such that the parser can report them together with
+/// their position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum FnToken {
+    Not,
+    And,
+    Or,
+    Xor,
+    Imp,
+    Iff,
+    Open,
+    Close,
+    Comma,
+    Name(String),
+    Invalid(String),
+}
+
+impl FnToken {
+    /// The number of characters this token occupies in the original string.
+    pub fn char_len(&self) -> usize {
+        self.to_string().chars().count()
+    }
+}
+
+impl Display for FnToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FnToken::Not => write!(f, "!"),
+            FnToken::And => write!(f, "&"),
+            FnToken::Or => write!(f, "|"),
+            FnToken::Xor => write!(f, "^"),
+            FnToken::Imp => write!(f, "=>"),
+            FnToken::Iff => write!(f, "<=>"),
+            FnToken::Open => write!(f, "("),
+            FnToken::Close => write!(f, ")"),
+            FnToken::Comma => write!(f, ","),
+            FnToken::Name(name) => write!(f, "{}", name),
+            FnToken::Invalid(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// True if the given character can appear in a variable or parameter name.
+pub(crate) fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '{' || c == '}'
+}
+
+/// Split an update function string into tokens. Each token is paired with the character
+/// (not byte) offset at which it starts.
+pub(crate) fn tokenize_update_function(function: &str) -> Vec<(usize, FnToken)> {
+    let chars: Vec<char> = function.chars().collect();
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        i += 1;
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '!' => FnToken::Not,
+            '&' => FnToken::And,
+            '|' => FnToken::Or,
+            '^' => FnToken::Xor,
+            '(' => FnToken::Open,
+            ')' => FnToken::Close,
+            ',' => FnToken::Comma,
+            '=' if chars.get(i) == Some(&'>') => {
+                i += 1;
+                FnToken::Imp
+            }
+            '<' if chars.get(i) == Some(&'=') && chars.get(i + 1) == Some(&'>') => {
+                i += 2;
+                FnToken::Iff
+            }
+            _ if is_name_char(c) => {
+                while i < chars.len() && is_name_char(chars[i]) {
+                    i += 1;
+                }
+                FnToken::Name(chars[start..i].iter().collect())
+            }
+            _ => FnToken::Invalid(c.to_string()),
+        };
+        result.push((start, token));
+    }
+    result
+}
+
+/// Returns the indices of all tokens that refer to the variable `name`, i.e. all occurrences
+/// of `name` that are not immediately followed by a parameter argument list.
+pub(crate) fn variable_references(tokens: &[(usize, FnToken)], name: &str) -> Vec<usize> {
+    let mut result = Vec::new();
+    for (i, (_, token)) in tokens.iter().enumerate() {
+        if let FnToken::Name(token_name) = token {
+            let is_parameter = matches!(tokens.get(i + 1), Some((_, FnToken::Open)));
+            if token_name == name && !is_parameter {
+                result.push(i);
+            }
+        }
+    }
+    result
+}
@@ -0,0 +1,157 @@
+use crate::boolean_network::{BooleanNetworkModel, RegulationData, VariableData};
+use crate::conversions::{write_metadata, ConversionError};
+use crate::Conversions;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Convert the model into an `.aeon` string. The string contains all regulations,
+    /// explicit update functions, the `#position:` layout metadata of every variable,
+    /// and the model name, description and annotations.
+    ///
+    /// Note that the `.aeon` format cannot represent variables with no regulations and
+    /// no update function. Such variables are lost when the result is parsed again.
+    pub fn to_aeon(&self) -> String {
+        let variables = self.sorted_variables();
+
+        let mut model_string = String::new();
+        for var in &variables {
+            let mut regulators = self.regulation_data[&var.id].iter().collect::<Vec<_>>();
+            regulators.sort_by_key(|reg| reg.source);
+            for reg in regulators {
+                model_string += format!(
+                    "{} {} {}\n",
+                    self.variable_data[&reg.source].name,
+                    regulation_arrow(reg),
+                    var.name,
+                )
+                .as_str();
+            }
+        }
+        for var in &variables {
+            let function = var.update_function.raw_string.trim();
+            if !function.is_empty() {
+                model_string += format!("${}: {}\n", var.name, function).as_str();
+            }
+        }
+        for var in &variables {
+            let (x, y) = var.position;
+            model_string += format!("#position:{}:{},{}\n", var.name, x, y).as_str();
+        }
+        model_string += write_metadata(&self.aeon_metadata()).as_str();
+        model_string
+    }
+
+    /// Convert the model into an `.sbml` string, preserving the model layout.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    pub fn to_sbml(&self) -> Result<String, ConversionError> {
+        Conversions::aeon_to_sbml(self.to_aeon().as_str())
+    }
+
+    /// Convert the model into a `.bnet` string. Layout and regulation monotonicity
+    /// are stored in `# aeon:` comments (see `Conversions::aeon_to_bnet`).
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    pub fn to_bnet(&self) -> Result<String, ConversionError> {
+        Conversions::aeon_to_bnet(self.to_aeon().as_str())
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** All variables of the model, sorted by their ID.
+    pub(crate) fn sorted_variables(&self) -> Vec<&VariableData> {
+        let mut variables = self.variable_data.values().collect::<Vec<_>>();
+        variables.sort_by_key(|var| var.id);
+        variables
+    }
+
+    /// **(internal)** All regulations of the model, sorted by their target and source ID.
+    pub(crate) fn sorted_regulations(&self) -> Vec<&RegulationData> {
+        let mut regulations = self.regulation_data.values().flatten().collect::<Vec<_>>();
+        regulations.sort_by_key(|reg| (reg.target, reg.source));
+        regulations
+    }
+}
+
+/// **(internal)** The `.aeon` arrow corresponding to the given regulation.
+fn regulation_arrow(regulation: &RegulationData) -> String {
+    let arrow = match regulation.monotonicity.as_str() {
+        "+" => "->",
+        "-" => "-|",
+        _ => "-?",
+    };
+    if regulation.observable {
+        arrow.to_string()
+    } else {
+        format!("{}?", arrow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boolean_network::BooleanNetworkModel;
+    use crate::Conversions;
+
+    #[test]
+    fn aeon_round_trip() {
+        let aeon = "a -> b\nb -|? a\nc -? b\n$a: !b\n$b: a & c\n\
+            #position:a:1,2\n#position:b:3.5,-4\n#position:c:0,0\n\
+            #name:Model\n#description:Text\n#note:a:Note\n#tag:b->a:curated\n";
+        let model = BooleanNetworkModel::from_aeon(aeon).unwrap();
+        let exported = model.to_aeon();
+        let copy = BooleanNetworkModel::from_aeon(&exported).unwrap();
+        assert_eq!(exported, copy.to_aeon());
+        assert!(copy.unplaced.is_empty());
+
+        let positions = copy
+            .sorted_variables()
+            .iter()
+            .map(|var| (var.name.as_str(), var.position))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![("a", (1.0, 2.0)), ("b", (3.5, -4.0)), ("c", (0.0, 0.0))],
+            positions
+        );
+        assert_eq!(model.metadata, copy.metadata);
+        assert_eq!("Model", copy.metadata.name);
+        // The variable `c` has no update function, but it is kept thanks to its regulation.
+        assert_eq!("", copy.variable_data[&2].update_function.raw_string);
+        let regulations = copy
+            .sorted_regulations()
+            .iter()
+            .map(|reg| {
+                (
+                    reg.source,
+                    reg.target,
+                    reg.observable,
+                    reg.monotonicity.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![(1, 0, false, "-"), (0, 1, true, "+"), (2, 1, true, "")],
+            regulations
+        );
+
+        // The same model survives a round trip through the other formats.
+        let sbml = copy.to_sbml().unwrap();
+        let from_sbml = BooleanNetworkModel::from_aeon(&Conversions::sbml_to_aeon(&sbml).unwrap());
+        assert_eq!(3, from_sbml.unwrap().variable_data.len());
+        // In `.bnet`, every variable needs an update function, so `c` becomes an input.
+        let mut instantiated = copy.clone();
+        instantiated
+            .add_regulation_internal(2, 2, true, "")
+            .unwrap();
+        instantiated
+            .variable_data
+            .get_mut(&2)
+            .unwrap()
+            .update_function
+            .raw_string = "c".to_string();
+        let bnet = instantiated.to_bnet().unwrap();
+        let from_bnet = Conversions::bnet_to_aeon(&bnet).unwrap();
+        let from_bnet = BooleanNetworkModel::from_aeon(&from_bnet).unwrap();
+        assert_eq!(instantiated.to_aeon(), from_bnet.to_aeon());
+    }
+}
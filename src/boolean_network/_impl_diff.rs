@@ -0,0 +1,228 @@
+use crate::boolean_network::{BooleanNetworkModel, RegulationData};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+/// Structural differences between two versions of a `BooleanNetworkModel`. Variables are
+/// matched by name, so all items are identified using variable names instead of IDs.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ModelDiff {
+    added_variables: Vec<String>,
+    removed_variables: Vec<String>,
+    added_regulations: Vec<RegulationDiff>,
+    removed_regulations: Vec<RegulationDiff>,
+    changed_regulations: Vec<RegulationChange>,
+    changed_update_functions: Vec<UpdateFunctionChange>,
+}
+
+/// A regulation that is present in only one of the compared models.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct RegulationDiff {
+    source: String,
+    target: String,
+    observable: bool,
+    monotonicity: String,
+}
+
+/// A regulation which is present in both models, but with different properties.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct RegulationChange {
+    source: String,
+    target: String,
+    old_observable: bool,
+    new_observable: bool,
+    old_monotonicity: String,
+    new_monotonicity: String,
+}
+
+/// An update function which differs between the two models (ignoring whitespace).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct UpdateFunctionChange {
+    variable: String,
+    old_function: String,
+    new_function: String,
+}
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Compute the structural differences between this model (the old version) and
+    /// the `other` model (the new version). Variables are matched by name.
+    ///
+    /// The result is an object with lists of `added_variables`, `removed_variables`,
+    /// `added_regulations`, `removed_regulations`, `changed_regulations` (monotonicity or
+    /// observability) and `changed_update_functions`.
+    pub fn diff(&self, other: &BooleanNetworkModel) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.compute_diff(other)).unwrap()
+    }
+
+    /// Same as `diff`, but the two models are given as `.aeon` strings.
+    ///
+    /// When one of the models is not valid, a `string` error is thrown.
+    pub fn diff_aeon(old_aeon: &str, new_aeon: &str) -> Result<JsValue, String> {
+        let old = BooleanNetworkModel::from_aeon(old_aeon)?;
+        let new = BooleanNetworkModel::from_aeon(new_aeon)?;
+        Ok(old.diff(&new))
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** Compute the `ModelDiff` between this model and the `other` model.
+    pub(crate) fn compute_diff(&self, other: &BooleanNetworkModel) -> ModelDiff {
+        let mut diff = ModelDiff::default();
+
+        let old_variables = self.variables_by_name();
+        let new_variables = other.variables_by_name();
+        for name in old_variables.keys() {
+            if !new_variables.contains_key(name) {
+                diff.removed_variables.push(name.to_string());
+            }
+        }
+        for (name, new_id) in &new_variables {
+            let Some(old_id) = old_variables.get(name) else {
+                diff.added_variables.push(name.to_string());
+                continue;
+            };
+            let old_function = &self.variable_data[old_id].update_function.raw_string;
+            let new_function = &other.variable_data[new_id].update_function.raw_string;
+            if strip_whitespace(old_function) != strip_whitespace(new_function) {
+                diff.changed_update_functions.push(UpdateFunctionChange {
+                    variable: name.to_string(),
+                    old_function: old_function.clone(),
+                    new_function: new_function.clone(),
+                });
+            }
+        }
+
+        let old_regulations = self.regulations_by_name();
+        let new_regulations = other.regulations_by_name();
+        for (key, old) in &old_regulations {
+            if !new_regulations.contains_key(key) {
+                diff.removed_regulations.push(RegulationDiff::new(key, old));
+            }
+        }
+        for (key, new) in &new_regulations {
+            let Some(old) = old_regulations.get(key) else {
+                diff.added_regulations.push(RegulationDiff::new(key, new));
+                continue;
+            };
+            if old.observable != new.observable || old.monotonicity != new.monotonicity {
+                diff.changed_regulations.push(RegulationChange {
+                    source: key.0.to_string(),
+                    target: key.1.to_string(),
+                    old_observable: old.observable,
+                    new_observable: new.observable,
+                    old_monotonicity: old.monotonicity.clone(),
+                    new_monotonicity: new.monotonicity.clone(),
+                });
+            }
+        }
+
+        diff.added_variables.sort();
+        diff.removed_variables.sort();
+        diff.added_regulations
+            .sort_by(|a, b| (&a.source, &a.target).cmp(&(&b.source, &b.target)));
+        diff.removed_regulations
+            .sort_by(|a, b| (&a.source, &a.target).cmp(&(&b.source, &b.target)));
+        diff.changed_regulations
+            .sort_by(|a, b| (&a.source, &a.target).cmp(&(&b.source, &b.target)));
+        diff.changed_update_functions
+            .sort_by(|a, b| a.variable.cmp(&b.variable));
+        diff
+    }
+
+    /// **(internal)** Map variable names to their IDs.
+    fn variables_by_name(&self) -> HashMap<&str, u64> {
+        self.variable_data
+            .values()
+            .map(|var| (var.name.as_str(), var.id))
+            .collect()
+    }
+
+    /// **(internal)** Map `(source, target)` name pairs to the data of the regulation.
+    fn regulations_by_name(&self) -> HashMap<(&str, &str), &RegulationData> {
+        self.regulation_data
+            .values()
+            .flatten()
+            .map(|reg| {
+                let source = self.variable_data[&reg.source].name.as_str();
+                let target = self.variable_data[&reg.target].name.as_str();
+                ((source, target), reg)
+            })
+            .collect()
+    }
+}
+
+impl RegulationDiff {
+    fn new(key: &(&str, &str), data: &RegulationData) -> RegulationDiff {
+        RegulationDiff {
+            source: key.0.to_string(),
+            target: key.1.to_string(),
+            observable: data.observable,
+            monotonicity: data.monotonicity.clone(),
+        }
+    }
+}
+
+/// **(internal)** Remove all whitespace from the given string.
+fn strip_whitespace(value: &str) -> String {
+    value.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boolean_network::BooleanNetworkModel;
+
+    #[test]
+    fn diff_of_two_models() {
+        let old = "a -> b\nb -| a\nb -> c\n$a: !b\n$b: a\n";
+        let new = "a -| b\nb -| a\na -> d\n$a: !b\n$b: !a\n";
+        let old = BooleanNetworkModel::from_aeon(old).unwrap();
+        let new = BooleanNetworkModel::from_aeon(new).unwrap();
+        let diff = old.compute_diff(&new);
+
+        assert_eq!(vec!["d"], diff.added_variables);
+        assert_eq!(vec!["c"], diff.removed_variables);
+        let regulation = |r: &super::RegulationDiff| (r.source.clone(), r.target.clone());
+        let added = diff
+            .added_regulations
+            .iter()
+            .map(regulation)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![("a".to_string(), "d".to_string())], added);
+        let removed = diff
+            .removed_regulations
+            .iter()
+            .map(regulation)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![("b".to_string(), "c".to_string())], removed);
+
+        assert_eq!(1, diff.changed_regulations.len());
+        let change = &diff.changed_regulations[0];
+        assert_eq!(("a", "b"), (change.source.as_str(), change.target.as_str()));
+        assert_eq!(
+            ("+", "-"),
+            (
+                change.old_monotonicity.as_str(),
+                change.new_monotonicity.as_str()
+            )
+        );
+
+        assert_eq!(1, diff.changed_update_functions.len());
+        let change = &diff.changed_update_functions[0];
+        assert_eq!("b", change.variable);
+        assert_eq!(
+            ("a", "!a"),
+            (change.old_function.as_str(), change.new_function.as_str())
+        );
+
+        // Whitespace changes are ignored.
+        let mut spaced = old.clone();
+        let function = &mut spaced.variable_data.get_mut(&0).unwrap().update_function;
+        function.raw_string = " ! b ".to_string();
+        assert!(old
+            .compute_diff(&spaced)
+            .changed_update_functions
+            .is_empty());
+    }
+}
@@ -0,0 +1,503 @@
+use crate::boolean_network::{
+    BooleanNetworkModel, EditCommand, ModelMetadata, RegulationData, VariableData,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Revert the last edit operation (or group of operations).
+    ///
+    /// Returns `false` if there is nothing to undo. If the operation does not fit the current
+    /// state of the model, the model is not modified and a `string` error is thrown.
+    pub fn undo(&mut self) -> Result<bool, String> {
+        self.end_edit_group();
+        if let Some(command) = self.history.undo_stack.pop() {
+            if let Err(error) = command.inverse().apply(self) {
+                self.history.undo_stack.push(command);
+                return Err(error);
+            }
+            self.history.redo_stack.push(command);
+            self.history.can_merge = false;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Apply the last reverted edit operation (or group of operations) again.
+    ///
+    /// Returns `false` if there is nothing to redo. If the operation does not fit the current
+    /// state of the model, the model is not modified and a `string` error is thrown.
+    pub fn redo(&mut self) -> Result<bool, String> {
+        self.end_edit_group();
+        if let Some(command) = self.history.redo_stack.pop() {
+            if let Err(error) = command.apply(self) {
+                self.history.redo_stack.push(command);
+                return Err(error);
+            }
+            self.history.undo_stack.push(command);
+            self.history.can_merge = false;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// The number of operations that can be reverted using `undo`.
+    pub fn history_len(&self) -> usize {
+        self.history.undo_stack.len()
+    }
+
+    /// The number of operations that can be applied again using `redo`.
+    pub fn redo_len(&self) -> usize {
+        self.history.redo_stack.len()
+    }
+
+    /// Ensure that the next edit is not merged with the previous one, even if it modifies
+    /// the same property (e.g. when the user starts a new drag of the same variable).
+    pub fn checkpoint(&mut self) {
+        self.history.can_merge = false;
+    }
+
+    /// Start a group of edits which will be reverted by a single `undo`. Groups cannot be
+    /// nested, so if a group is already open, this has no effect.
+    pub fn begin_edit_group(&mut self) {
+        if self.history.open_group.is_none() {
+            self.history.open_group = Some(Vec::new());
+        }
+    }
+
+    /// Finish the currently open group of edits (if any).
+    pub fn end_edit_group(&mut self) {
+        if let Some(group) = self.history.open_group.take() {
+            if !group.is_empty() {
+                self.history.undo_stack.push(EditCommand::Group(group));
+                self.history.can_merge = false;
+            }
+        }
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** Apply the given command to this model and record it in the edit history.
+    ///
+    /// If the command cannot be applied, the model and its history are not modified.
+    pub(crate) fn execute(&mut self, command: EditCommand) -> Result<(), String> {
+        command.apply(self)?;
+        let history = &mut self.history;
+        history.redo_stack.clear();
+        if let Some(group) = history.open_group.as_mut() {
+            let is_merged = group.last_mut().map(|last| last.merge(&command));
+            if is_merged != Some(true) {
+                group.push(command);
+            }
+            return Ok(());
+        }
+        if history.can_merge {
+            if let Some(last) = history.undo_stack.last_mut() {
+                if last.merge(&command) {
+                    return Ok(());
+                }
+            }
+        }
+        history.undo_stack.push(command);
+        history.can_merge = true;
+        Ok(())
+    }
+
+    /// **(internal)** Mutable data of the variable with the given `id`.
+    fn variable_mut(&mut self, id: u64) -> Result<&mut VariableData, String> {
+        self.variable_data
+            .get_mut(&id)
+            .ok_or_else(|| format!("Value {} is not a valid variable ID.", id))
+    }
+
+    /// **(internal)** Mutable list of regulations of the variable with the given `id`.
+    fn regulators_mut(&mut self, id: u64) -> Result<&mut Vec<RegulationData>, String> {
+        self.regulation_data
+            .get_mut(&id)
+            .ok_or_else(|| format!("Value {} is not a valid variable ID.", id))
+    }
+}
+
+impl EditCommand {
+    /// **(internal)** Perform the modification described by this command. If the command
+    /// does not fit the current state of the model, an error is returned and the model
+    /// is not modified.
+    fn apply(&self, model: &mut BooleanNetworkModel) -> Result<(), String> {
+        match self {
+            EditCommand::AddVariable { variable, unplaced } => {
+                if model.variable_data.contains_key(&variable.id) {
+                    return Err(format!("Variable ID {} is already used.", variable.id));
+                }
+                model.variable_data.insert(variable.id, variable.clone());
+                model.regulation_data.insert(variable.id, Vec::new());
+                if *unplaced {
+                    model.unplaced.insert(variable.id);
+                }
+            }
+            EditCommand::RemoveVariable { variable, .. } => {
+                let id = variable.id;
+                model.get_variable_data(id)?;
+                model.variable_data.remove(&id);
+                model.regulation_data.remove(&id);
+                model.unplaced.remove(&id);
+                for regulators in model.regulation_data.values_mut() {
+                    regulators.retain(|reg| reg.source != id);
+                }
+            }
+            EditCommand::SetName { id, new, .. } => {
+                model.variable_mut(*id)?.name = new.clone();
+            }
+            EditCommand::SetPosition {
+                id,
+                new,
+                new_unplaced,
+                ..
+            } => {
+                model.variable_mut(*id)?.position = *new;
+                if *new_unplaced {
+                    model.unplaced.insert(*id);
+                } else {
+                    model.unplaced.remove(id);
+                }
+            }
+            EditCommand::SetUpdateFunction { id, new, .. } => {
+                model.variable_mut(*id)?.update_function.raw_string = new.clone();
+            }
+            EditCommand::AddRegulation(data) => {
+                model.regulators_mut(data.target)?.push(data.clone());
+            }
+            EditCommand::RemoveRegulation(data) => {
+                let regulators = model.regulators_mut(data.target)?;
+                regulators.retain(|reg| reg.source != data.source);
+            }
+            EditCommand::UpdateRegulation { new, .. } => {
+                let regulators = model.regulators_mut(new.target)?;
+                for reg in regulators.iter_mut().filter(|reg| reg.source == new.source) {
+                    *reg = new.clone();
+                }
+            }
+            EditCommand::SetMetadata { new, .. } => {
+                model.metadata = new.as_ref().clone();
+            }
+            EditCommand::Group(commands) => {
+                for (i, command) in commands.iter().enumerate() {
+                    if let Err(error) = command.apply(model) {
+                        // Revert the commands which were already applied.
+                        for command in commands[..i].iter().rev() {
+                            command.inverse().apply(model)?;
+                        }
+                        return Err(error);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Create a command which reverts the effects of this command.
+    fn inverse(&self) -> EditCommand {
+        match self {
+            EditCommand::AddVariable { variable, unplaced } => EditCommand::RemoveVariable {
+                variable: variable.clone(),
+                regulations: Vec::new(),
+                unplaced: *unplaced,
+            },
+            EditCommand::RemoveVariable {
+                variable,
+                regulations,
+                unplaced,
+            } => {
+                let mut commands = vec![EditCommand::AddVariable {
+                    variable: variable.clone(),
+                    unplaced: *unplaced,
+                }];
+                commands.extend(regulations.iter().cloned().map(EditCommand::AddRegulation));
+                EditCommand::Group(commands)
+            }
+            EditCommand::SetName { id, old, new } => EditCommand::SetName {
+                id: *id,
+                old: new.clone(),
+                new: old.clone(),
+            },
+            EditCommand::SetPosition {
+                id,
+                old,
+                new,
+                old_unplaced,
+                new_unplaced,
+            } => EditCommand::SetPosition {
+                id: *id,
+                old: *new,
+                new: *old,
+                old_unplaced: *new_unplaced,
+                new_unplaced: *old_unplaced,
+            },
+            EditCommand::SetUpdateFunction { id, old, new } => EditCommand::SetUpdateFunction {
+                id: *id,
+                old: new.clone(),
+                new: old.clone(),
+            },
+            EditCommand::AddRegulation(data) => EditCommand::RemoveRegulation(data.clone()),
+            EditCommand::RemoveRegulation(data) => EditCommand::AddRegulation(data.clone()),
+            EditCommand::UpdateRegulation { old, new } => EditCommand::UpdateRegulation {
+                old: new.clone(),
+                new: old.clone(),
+            },
+            EditCommand::SetMetadata { old, new } => EditCommand::SetMetadata {
+                old: new.clone(),
+                new: old.clone(),
+            },
+            EditCommand::Group(commands) => {
+                EditCommand::Group(commands.iter().rev().map(|c| c.inverse()).collect())
+            }
+        }
+    }
+
+    /// **(internal)** Try to merge the `next` command into this command. This is only
+    /// possible if both commands modify the same property of the same object.
+    ///
+    /// Returns `true` if the commands were merged.
+    fn merge(&mut self, next: &EditCommand) -> bool {
+        match (self, next) {
+            (
+                EditCommand::SetPosition {
+                    id,
+                    new,
+                    new_unplaced,
+                    ..
+                },
+                EditCommand::SetPosition {
+                    id: next_id,
+                    new: next_new,
+                    new_unplaced: next_unplaced,
+                    ..
+                },
+            ) if id == next_id => {
+                *new = *next_new;
+                *new_unplaced = *next_unplaced;
+                true
+            }
+            (
+                EditCommand::SetUpdateFunction { id, new, .. },
+                EditCommand::SetUpdateFunction {
+                    id: next_id,
+                    new: next_new,
+                    ..
+                },
+            ) if id == next_id => {
+                *new = next_new.clone();
+                true
+            }
+            (
+                EditCommand::UpdateRegulation { new, .. },
+                EditCommand::UpdateRegulation { new: next_new, .. },
+            ) if new.source == next_new.source && new.target == next_new.target => {
+                *new = next_new.clone();
+                true
+            }
+            (
+                EditCommand::SetMetadata { old, new },
+                EditCommand::SetMetadata {
+                    old: next_old,
+                    new: next_new,
+                },
+            ) if changed_metadata(old, new) == changed_metadata(next_old, next_new) => {
+                *new = next_new.clone();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// **(internal)** A part of `ModelMetadata` which can be edited on its own.
+#[derive(PartialEq)]
+enum MetadataField {
+    Name,
+    Description,
+    Variable(u64),
+    Regulation(u64, u64),
+}
+
+/// **(internal)** List the fields which differ between the `old` and `new` metadata.
+fn changed_metadata(old: &ModelMetadata, new: &ModelMetadata) -> Vec<MetadataField> {
+    let mut result = Vec::new();
+    if old.name != new.name {
+        result.push(MetadataField::Name);
+    }
+    if old.description != new.description {
+        result.push(MetadataField::Description);
+    }
+    let variables = old.variables.keys().chain(new.variables.keys());
+    for id in variables.collect::<BTreeSet<_>>() {
+        if old.variables.get(id) != new.variables.get(id) {
+            result.push(MetadataField::Variable(*id));
+        }
+    }
+    let annotations = |metadata: &ModelMetadata| {
+        metadata
+            .regulations
+            .iter()
+            .flat_map(|(source, targets)| {
+                targets
+                    .iter()
+                    .map(move |(target, annotations)| ((*source, *target), annotations.clone()))
+            })
+            .collect::<BTreeMap<_, _>>()
+    };
+    let (old, new) = (annotations(old), annotations(new));
+    for key in old.keys().chain(new.keys()).collect::<BTreeSet<_>>() {
+        if old.get(key) != new.get(key) {
+            result.push(MetadataField::Regulation(key.0, key.1));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boolean_network::{
+        BooleanNetworkModel, EditCommand, ModelMetadata, UpdateFunctionData, VariableData,
+    };
+    use crate::conversions::Annotations;
+
+    fn add_variable(id: u64, unplaced: bool) -> EditCommand {
+        EditCommand::AddVariable {
+            variable: VariableData {
+                id,
+                name: format!("v_{}", id),
+                update_function: UpdateFunctionData {
+                    raw_string: String::new(),
+                },
+                position: (0.0, 0.0),
+            },
+            unplaced,
+        }
+    }
+
+    #[test]
+    fn undo_redo_with_merged_edits() {
+        let mut model = BooleanNetworkModel::new();
+        model.execute(add_variable(1, true)).unwrap();
+        for i in 1..5 {
+            model
+                .execute(EditCommand::SetPosition {
+                    id: 1,
+                    old: model.variable_data[&1].position,
+                    new: (i as f64, 0.0),
+                    old_unplaced: model.unplaced.contains(&1),
+                    new_unplaced: false,
+                })
+                .unwrap();
+        }
+        assert_eq!(2, model.history_len());
+        assert!(model.unplaced.is_empty());
+
+        assert_eq!(Ok(true), model.undo());
+        assert_eq!((0.0, 0.0), model.variable_data[&1].position);
+        assert!(model.unplaced.contains(&1));
+        assert_eq!(Ok(true), model.undo());
+        assert!(model.is_empty());
+        assert!(model.unplaced.is_empty());
+        assert_eq!(Ok(false), model.undo());
+
+        assert_eq!(Ok(true), model.redo());
+        assert!(model.unplaced.contains(&1));
+        assert_eq!(Ok(true), model.redo());
+        assert_eq!((4.0, 0.0), model.variable_data[&1].position);
+        assert!(model.unplaced.is_empty());
+        assert_eq!(0, model.redo_len());
+    }
+
+    #[test]
+    fn undo_edit_group() {
+        let mut model = BooleanNetworkModel::new();
+        model.begin_edit_group();
+        model.execute(add_variable(1, false)).unwrap();
+        model.execute(add_variable(2, false)).unwrap();
+        model.end_edit_group();
+        assert_eq!(1, model.history_len());
+        assert_eq!(Ok(true), model.undo());
+        assert!(model.is_empty());
+    }
+
+    #[test]
+    fn invalid_commands_are_rejected() {
+        let mut model = BooleanNetworkModel::new();
+        model.execute(add_variable(1, false)).unwrap();
+        let rename = EditCommand::SetName {
+            id: 2,
+            old: "v_2".to_string(),
+            new: "x".to_string(),
+        };
+        assert!(model.execute(rename.clone()).is_err());
+        assert_eq!(1, model.history_len());
+
+        // A failed group leaves the model unchanged.
+        let group = EditCommand::Group(vec![add_variable(3, true), rename]);
+        assert!(model.execute(group).is_err());
+        assert!(!model.variable_data.contains_key(&3));
+        assert!(model.unplaced.is_empty());
+        assert_eq!(1, model.history_len());
+    }
+
+    #[test]
+    fn merge_metadata_edits_of_the_same_field() {
+        let mut model = BooleanNetworkModel::new();
+        let edit = |model: &mut BooleanNetworkModel, change: &dyn Fn(&mut ModelMetadata)| {
+            let mut metadata = model.metadata.clone();
+            change(&mut metadata);
+            model
+                .execute(EditCommand::SetMetadata {
+                    old: Box::new(model.metadata.clone()),
+                    new: Box::new(metadata),
+                })
+                .unwrap();
+        };
+        edit(&mut model, &|m| m.name = "M".to_string());
+        edit(&mut model, &|m| m.name = "Model".to_string());
+        assert_eq!(1, model.history_len());
+        edit(&mut model, &|m| m.description = "Text".to_string());
+        assert_eq!(2, model.history_len());
+        let note = |text: &str| Annotations {
+            notes: vec![text.to_string()],
+            ..Default::default()
+        };
+        edit(&mut model, &|m| {
+            m.variables.insert(1, note("a"));
+        });
+        edit(&mut model, &|m| {
+            m.variables.insert(2, note("b"));
+        });
+        edit(&mut model, &|m| {
+            m.variables.insert(2, note("c"));
+        });
+        assert_eq!(4, model.history_len());
+
+        assert_eq!(Ok(true), model.undo());
+        assert_eq!(Some(&note("a")), model.metadata.variables.get(&1));
+        assert!(!model.metadata.variables.contains_key(&2));
+        assert_eq!(Ok(true), model.undo());
+        assert_eq!(Ok(true), model.undo());
+        assert_eq!("Model", model.metadata.name);
+        assert_eq!("", model.metadata.description);
+    }
+
+    #[test]
+    fn invalid_variables_are_not_added() {
+        let mut model = BooleanNetworkModel::new();
+        assert!(model.add_variable_internal(None, Some(vec![1.0])).is_err());
+        assert_eq!(0, model.history_len());
+        assert_eq!(
+            Ok(1),
+            model.add_variable_internal(None, Some(vec![1.0, 2.0]))
+        );
+        assert_eq!((1.0, 2.0), model.variable_data[&1].position);
+        assert!(model.unplaced.is_empty());
+        assert_eq!(Ok(2), model.add_variable_internal(None, None));
+        assert!(model.unplaced.contains(&2));
+    }
+}
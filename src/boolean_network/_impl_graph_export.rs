@@ -0,0 +1,161 @@
+use crate::boolean_network::{BooleanNetworkModel, RegulationData};
+use json::{array, object, JsonValue};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Export the regulatory graph of this model in the Graphviz `.dot` format.
+    ///
+    /// Activations are green with a normal arrow head, inhibitions are red with a "tee"
+    /// arrow head, and non-observable regulations are dashed. Variable positions are
+    /// exported as fixed `pos` attributes (with the `y` axis flipped, since Graphviz
+    /// uses a bottom-up coordinate system).
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph G {\n");
+        for var in self.sorted_variables() {
+            let (x, y) = var.position;
+            let name = dot_string(&var.name);
+            dot += format!("{} [label={}, pos=\"{},{}!\"];\n", name, name, x, -y).as_str();
+        }
+        for reg in self.sorted_regulations() {
+            let (color, arrowhead) = match reg.monotonicity.as_str() {
+                "+" => ("darkgreen", "normal"),
+                "-" => ("red", "tee"),
+                _ => ("black", "odot"),
+            };
+            let style = if reg.observable { "solid" } else { "dashed" };
+            dot += format!(
+                "{} -> {} [color={}, arrowhead={}, style={}];\n",
+                dot_string(&self.variable_data[&reg.source].name),
+                dot_string(&self.variable_data[&reg.target].name),
+                color,
+                arrowhead,
+                style,
+            )
+            .as_str();
+        }
+        dot += "}\n";
+        dot
+    }
+
+    /// Export the regulatory graph of this model in the `.graphml` format.
+    ///
+    /// Nodes carry `name`, `x` and `y` attributes, edges carry a `sign` (`+`, `-`, or empty)
+    /// and an `observable` attribute.
+    pub fn to_graphml(&self) -> String {
+        let mut graphml = String::new();
+        graphml += "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n";
+        graphml += "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n";
+        graphml += "  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n";
+        graphml += "  <key id=\"x\" for=\"node\" attr.name=\"x\" attr.type=\"double\"/>\n";
+        graphml += "  <key id=\"y\" for=\"node\" attr.name=\"y\" attr.type=\"double\"/>\n";
+        graphml += "  <key id=\"sign\" for=\"edge\" attr.name=\"sign\" attr.type=\"string\"/>\n";
+        graphml += "  <key id=\"observable\" for=\"edge\" attr.name=\"observable\" attr.type=\"boolean\"/>\n";
+        graphml += "  <graph id=\"G\" edgedefault=\"directed\">\n";
+        for var in self.sorted_variables() {
+            let (x, y) = var.position;
+            graphml += format!("    <node id=\"{}\">\n", var.name).as_str();
+            graphml += format!("      <data key=\"name\">{}</data>\n", var.name).as_str();
+            graphml += format!("      <data key=\"x\">{}</data>\n", x).as_str();
+            graphml += format!("      <data key=\"y\">{}</data>\n", y).as_str();
+            graphml += "    </node>\n";
+        }
+        for reg in self.sorted_regulations() {
+            graphml += format!(
+                "    <edge source=\"{}\" target=\"{}\">\n",
+                self.variable_data[&reg.source].name, self.variable_data[&reg.target].name,
+            )
+            .as_str();
+            graphml += format!("      <data key=\"sign\">{}</data>\n", reg.monotonicity).as_str();
+            graphml +=
+                format!("      <data key=\"observable\">{}</data>\n", reg.observable).as_str();
+            graphml += "    </edge>\n";
+        }
+        graphml += "  </graph>\n";
+        graphml += "</graphml>\n";
+        graphml
+    }
+
+    /// Export the regulatory graph of this model as a Cytoscape.js JSON string.
+    ///
+    /// Node ids are variable names. Every edge has an `interaction` (`activation`,
+    /// `inhibition` or `unknown`), a `sign` (`+`, `-`, or empty) and an `observable` field.
+    /// The included style draws non-observable regulations as dashed lines.
+    pub fn to_cytoscape(&self) -> String {
+        let mut nodes = JsonValue::new_array();
+        for var in self.sorted_variables() {
+            let (x, y) = var.position;
+            nodes
+                .push(object! {
+                    "data": object! { "id": var.name.clone(), "name": var.name.clone() },
+                    "position": object! { "x": x, "y": y },
+                })
+                .unwrap();
+        }
+        let mut edges = JsonValue::new_array();
+        for reg in self.sorted_regulations() {
+            let source = self.variable_data[&reg.source].name.clone();
+            let target = self.variable_data[&reg.target].name.clone();
+            edges
+                .push(object! {
+                    "data": object! {
+                        "id": format!("{}_{}", source, target),
+                        "source": source,
+                        "target": target,
+                        "interaction": interaction(reg),
+                        "sign": reg.monotonicity.clone(),
+                        "observable": reg.observable,
+                    },
+                    "classes": if reg.observable { "" } else { "non-observable" },
+                })
+                .unwrap();
+        }
+        let style = array![
+            object! {
+                "selector": "edge[interaction = \"activation\"]",
+                "style": object! { "line-color": "#4abd73", "target-arrow-color": "#4abd73", "target-arrow-shape": "triangle" },
+            },
+            object! {
+                "selector": "edge[interaction = \"inhibition\"]",
+                "style": object! { "line-color": "#d05d5d", "target-arrow-color": "#d05d5d", "target-arrow-shape": "tee" },
+            },
+            object! {
+                "selector": "edge.non-observable",
+                "style": object! { "line-style": "dashed" },
+            },
+        ];
+        let result = object! {
+            "elements": object! { "nodes": nodes, "edges": edges },
+            "style": style,
+        };
+        result.pretty(2)
+    }
+}
+
+/// **(internal)** A quoted `.dot` string, such that names which are keywords (e.g. `node`)
+/// or start with a digit are valid node IDs.
+fn dot_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// **(internal)** The Cytoscape `interaction` type of a regulation.
+fn interaction(regulation: &RegulationData) -> &'static str {
+    match regulation.monotonicity.as_str() {
+        "+" => "activation",
+        "-" => "inhibition",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boolean_network::BooleanNetworkModel;
+
+    #[test]
+    fn dot_keywords_are_quoted() {
+        let model = BooleanNetworkModel::from_aeon("node -> edge\n#position:node:1,2\n").unwrap();
+        let dot = model.to_dot();
+        assert!(dot.contains("\"node\" [label=\"node\", pos=\"1,-2!\"];\n"));
+        assert!(dot.contains("\"node\" -> \"edge\" [color=darkgreen"));
+    }
+}
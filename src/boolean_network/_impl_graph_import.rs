@@ -0,0 +1,349 @@
+use crate::boolean_network::{
+    BooleanNetworkModel, RegulationData, UpdateFunctionData, VariableData,
+};
+use crate::conversions::ConversionErrorKind::{Syntax, UnknownVariable};
+use crate::conversions::{ConversionError, ConversionErrorKind};
+use crate::utils::infer_new_position;
+use json::JsonValue;
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// **(internal)** A node of an imported interaction graph.
+struct GraphNode {
+    id: String,
+    name: String,
+    position: Option<(f64, f64)>,
+}
+
+/// **(internal)** An edge of an imported interaction graph. Source and target are node ids.
+struct GraphEdge {
+    source: String,
+    target: String,
+    monotonicity: String,
+    observable: bool,
+}
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Create a model from a regulatory graph in the `.graphml` format.
+    ///
+    /// Node names are read from a `name` or `label` attribute (or a yEd node label), and
+    /// positions from `x`/`y` attributes (or yEd node geometry). Edge monotonicity is read
+    /// from a `sign`, `interaction`, `monotonicity` or `effect` attribute, and observability
+    /// from an `observable` attribute. All update functions are left implicit.
+    ///
+    /// When the graph is not valid, a `ConversionError` is thrown.
+    pub fn from_graphml(graphml_string: &str) -> Result<BooleanNetworkModel, ConversionError> {
+        let error = |kind, message: &str| ConversionError::new("graphml", kind, message.into());
+        let document = roxmltree::Document::parse(graphml_string)
+            .map_err(|e| ConversionError::from_xml("graphml", e))?;
+
+        // Map key ids to (lowercase) attribute names.
+        let mut keys = HashMap::new();
+        for key in document.descendants().filter(|n| n.has_tag_name("key")) {
+            if let Some(id) = key.attribute("id") {
+                let name = key.attribute("attr.name").unwrap_or(id);
+                keys.insert(id.to_string(), name.to_lowercase());
+            }
+        }
+        let data_of = |node: roxmltree::Node| {
+            let mut data = HashMap::new();
+            for child in node.children().filter(|n| n.has_tag_name("data")) {
+                if let Some(key) = child.attribute("key") {
+                    let name = keys.get(key).cloned().unwrap_or_else(|| key.to_lowercase());
+                    let value = child.text().unwrap_or("").trim().to_string();
+                    data.insert(name, value);
+                }
+            }
+            data
+        };
+
+        let mut nodes = Vec::new();
+        for node in document.descendants().filter(|n| n.has_tag_name("node")) {
+            let id = node
+                .attribute("id")
+                .ok_or_else(|| error(Syntax, "GraphML node is missing an `id`.").at_node(node))?;
+            let data = data_of(node);
+            // yEd stores labels and coordinates in its own elements.
+            let yed_label = node
+                .descendants()
+                .find(|n| n.tag_name().name() == "NodeLabel")
+                .and_then(|n| n.text())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty());
+            let yed_position = node
+                .descendants()
+                .find(|n| n.tag_name().name() == "Geometry")
+                .and_then(|n| {
+                    Some((
+                        n.attribute("x")?.parse().ok()?,
+                        n.attribute("y")?.parse().ok()?,
+                    ))
+                });
+            let name = data
+                .get("name")
+                .or_else(|| data.get("label"))
+                .cloned()
+                .or(yed_label)
+                .unwrap_or_else(|| id.to_string());
+            let position = match (data.get("x"), data.get("y")) {
+                (Some(x), Some(y)) => x.parse().ok().zip(y.parse().ok()),
+                _ => None,
+            }
+            .or(yed_position);
+            nodes.push(GraphNode {
+                id: id.to_string(),
+                name,
+                position,
+            });
+        }
+
+        let mut edges = Vec::new();
+        for edge in document.descendants().filter(|n| n.has_tag_name("edge")) {
+            let (source, target) = match (edge.attribute("source"), edge.attribute("target")) {
+                (Some(source), Some(target)) => (source, target),
+                _ => {
+                    let message = "GraphML edge is missing a `source` or `target`.";
+                    return Err(error(Syntax, message).at_node(edge));
+                }
+            };
+            let data = data_of(edge);
+            let sign = ["sign", "interaction", "monotonicity", "effect"]
+                .iter()
+                .find_map(|key| data.get(*key));
+            edges.push(GraphEdge {
+                source: source.to_string(),
+                target: target.to_string(),
+                monotonicity: sign.map(|s| parse_sign(s)).unwrap_or_default(),
+                observable: data
+                    .get("observable")
+                    .map(|s| s.to_lowercase() != "false")
+                    .unwrap_or(true),
+            });
+        }
+
+        Self::from_graph("graphml", nodes, edges)
+    }
+
+    /// Create a model from a regulatory graph in the Cytoscape.js JSON format.
+    ///
+    /// Both the `{ "elements": { "nodes": [...], "edges": [...] } }` and the flat
+    /// `{ "elements": [...] }` layout are supported. Node names are read from the `name`
+    /// or `label` data field (falling back to the node `id`), edge monotonicity from
+    /// the `sign`, `interaction`, `monotonicity` or `effect` data field, and observability
+    /// from the `observable` data field. All update functions are left implicit.
+    ///
+    /// When the graph is not valid, a `ConversionError` is thrown.
+    pub fn from_cytoscape(json_string: &str) -> Result<BooleanNetworkModel, ConversionError> {
+        let error = |kind, message: &str| ConversionError::new("cytoscape", kind, message.into());
+        let json =
+            json::parse(json_string).map_err(|e| ConversionError::from_json("cytoscape", e))?;
+        let elements = if json.has_key("elements") {
+            &json["elements"]
+        } else {
+            &json
+        };
+        let (node_elements, edge_elements): (Vec<&JsonValue>, Vec<&JsonValue>) =
+            if elements.is_array() {
+                elements.members().partition(|e| {
+                    if e.has_key("group") {
+                        e["group"] == "nodes"
+                    } else {
+                        !e["data"].has_key("source")
+                    }
+                })
+            } else {
+                (
+                    elements["nodes"].members().collect(),
+                    elements["edges"].members().collect(),
+                )
+            };
+
+        let mut nodes = Vec::new();
+        for node in node_elements {
+            let data = &node["data"];
+            let id = json_string_value(&data["id"])
+                .ok_or_else(|| error(Syntax, "Cytoscape node is missing an `id`."))?;
+            let name = json_string_value(&data["name"])
+                .or_else(|| json_string_value(&data["label"]))
+                .unwrap_or_else(|| id.clone());
+            let position = node["position"]["x"]
+                .as_f64()
+                .zip(node["position"]["y"].as_f64());
+            nodes.push(GraphNode { id, name, position });
+        }
+
+        let mut edges = Vec::new();
+        for edge in edge_elements {
+            let data = &edge["data"];
+            let source = json_string_value(&data["source"]);
+            let target = json_string_value(&data["target"]);
+            let (source, target) = match (source, target) {
+                (Some(source), Some(target)) => (source, target),
+                _ => {
+                    let message = "Cytoscape edge is missing a `source` or `target`.";
+                    return Err(error(Syntax, message));
+                }
+            };
+            let sign = ["sign", "interaction", "monotonicity", "effect"]
+                .iter()
+                .find_map(|key| json_string_value(&data[*key]));
+            edges.push(GraphEdge {
+                source,
+                target,
+                monotonicity: sign.map(|s| parse_sign(&s)).unwrap_or_default(),
+                observable: data["observable"].as_bool().unwrap_or(true),
+            });
+        }
+
+        Self::from_graph("cytoscape", nodes, edges)
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** Build a model from the nodes and edges of an interaction graph.
+    ///
+    /// Node names are sanitized to valid variable names. Nodes without a position are
+    /// placed automatically. Parallel edges are merged into a single regulation, which
+    /// has an unknown monotonicity if the edges disagree. Errors refer to the given `format`.
+    fn from_graph(
+        format: &str,
+        nodes: Vec<GraphNode>,
+        edges: Vec<GraphEdge>,
+    ) -> Result<BooleanNetworkModel, ConversionError> {
+        let error = |kind: ConversionErrorKind, message: String, id: &str| {
+            ConversionError::new(format, kind, message).with_identifier(id)
+        };
+        let mut model = BooleanNetworkModel::new();
+        let mut node_ids = HashMap::new();
+        let mut used_names = HashSet::new();
+        for node in nodes {
+            if node_ids.contains_key(&node.id) {
+                let message = format!("Duplicate node id `{}`.", node.id);
+                return Err(error(Syntax, message, &node.id));
+            }
+            let id = model.id_counter;
+            model.id_counter += 1;
+            let name = unique_name(&sanitize_name(&node.name), &used_names);
+            used_names.insert(name.clone());
+            let position = node.position.unwrap_or_else(|| {
+                model.unplaced.insert(id);
+                infer_new_position(&model.variable_data)
+            });
+            model.variable_data.insert(
+                id,
+                VariableData {
+                    id,
+                    name,
+                    update_function: UpdateFunctionData {
+                        raw_string: String::new(),
+                    },
+                    position,
+                },
+            );
+            model.regulation_data.insert(id, Vec::new());
+            node_ids.insert(node.id, id);
+        }
+
+        for edge in edges {
+            let find = |node: &String| {
+                node_ids.get(node).cloned().ok_or_else(|| {
+                    let message = format!("Edge references unknown node `{}`.", node);
+                    error(UnknownVariable, message, node)
+                })
+            };
+            let (source, target) = (find(&edge.source)?, find(&edge.target)?);
+            let regulators = model.regulation_data.get_mut(&target).unwrap();
+            if let Some(existing) = regulators.iter_mut().find(|r| r.source == source) {
+                if existing.monotonicity != edge.monotonicity {
+                    existing.monotonicity = String::new();
+                }
+                existing.observable = existing.observable || edge.observable;
+            } else {
+                regulators.push(RegulationData {
+                    source,
+                    target,
+                    observable: edge.observable,
+                    monotonicity: edge.monotonicity,
+                });
+            }
+        }
+
+        Ok(model)
+    }
+}
+
+/// **(internal)** Interpret the common ways of describing an edge sign as a monotonicity
+/// string (`"+"`, `"-"` or `""`).
+fn parse_sign(value: &str) -> String {
+    match value.trim().to_lowercase().as_str() {
+        "+" | "1" | "->" | "positive" | "activation" | "activates" | "activator"
+        | "stimulation" | "promotes" => "+".to_string(),
+        "-" | "-1" | "-|" | "negative" | "inhibition" | "inhibits" | "inhibitor" | "repression"
+        | "represses" => "-".to_string(),
+        _ => String::new(),
+    }
+}
+
+/// **(internal)** Read a JSON string or number as a string.
+fn json_string_value(value: &JsonValue) -> Option<String> {
+    if value.is_number() {
+        Some(value.dump())
+    } else {
+        value.as_str().map(|s| s.to_string())
+    }
+}
+
+/// **(internal)** Replace all characters that are not allowed in variable names with `_`.
+fn sanitize_name(name: &str) -> String {
+    let name = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if name.is_empty() {
+        "v".to_string()
+    } else {
+        name
+    }
+}
+
+/// **(internal)** Append a numeric suffix to `name` if it is already used.
+fn unique_name(name: &str, used: &HashSet<String>) -> String {
+    if !used.contains(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|i| format!("{}_{}", name, i))
+        .find(|candidate| !used.contains(candidate))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boolean_network::BooleanNetworkModel;
+
+    #[test]
+    fn import_interaction_graph() {
+        let cytoscape = r#"{ "elements": [
+            { "data": { "id": "1", "name": "NF-kB" }, "position": { "x": 10, "y": 20 } },
+            { "data": { "id": "2", "label": "IkB" } },
+            { "data": { "source": "1", "target": "2", "interaction": "activation" } },
+            { "data": { "source": "2", "target": "1", "sign": "-", "observable": false } }
+        ] }"#;
+        let model = BooleanNetworkModel::from_cytoscape(cytoscape).unwrap();
+        let aeon = model.to_aeon();
+        assert!(aeon.contains("NF_kB -> IkB\n"));
+        assert!(aeon.contains("IkB -|? NF_kB\n"));
+        assert!(aeon.contains("#position:NF_kB:10,20\n"));
+
+        let imported = BooleanNetworkModel::from_graphml(&model.to_graphml()).unwrap();
+        assert_eq!(aeon, imported.to_aeon());
+    }
+}
@@ -0,0 +1,366 @@
+use crate::boolean_network::_impl_regulations::check_monotonicity;
+use crate::boolean_network::{
+    BooleanNetworkModel, EditHistory, ModelMetadata, RegulationData, UpdateFunctionData,
+    VariableData,
+};
+use crate::conversions::Annotations;
+use json::{object, JsonValue};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// The version of the JSON model format produced by `BooleanNetworkModel::to_json`.
+///
+/// Increase this number whenever the format changes in a way that older versions
+/// cannot read.
+const JSON_FORMAT_VERSION: u32 = 1;
+
+/// **(internal)** The serialized form of a `BooleanNetworkModel`.
+///
+/// Fields added in later versions of the format must be optional when reading, such that
+/// older files can still be loaded.
+struct ModelFile {
+    version: u32,
+    id_counter: u64,
+    variables: Vec<VariableData>,
+    regulations: Vec<RegulationData>,
+    unplaced: Vec<u64>,
+    metadata: ModelMetadata,
+}
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Serialize the complete state of the model (except for the undo/redo history) into
+    /// a versioned JSON string.
+    ///
+    /// Unlike `to_aeon`, the result preserves variable IDs, so a model loaded using
+    /// `from_json` is indistinguishable from the original.
+    pub fn to_json(&self) -> String {
+        let mut unplaced = self.unplaced.iter().cloned().collect::<Vec<_>>();
+        unplaced.sort();
+        let file = ModelFile {
+            version: JSON_FORMAT_VERSION,
+            id_counter: self.id_counter,
+            variables: self.sorted_variables().into_iter().cloned().collect(),
+            regulations: self.sorted_regulations().into_iter().cloned().collect(),
+            unplaced,
+            metadata: self.metadata.clone(),
+        };
+        file.to_json().dump()
+    }
+
+    /// Load a model from a JSON string created by `to_json`.
+    ///
+    /// When the string is not a valid model, or it was created by a newer version of the
+    /// format, a `string` error is thrown.
+    pub fn from_json(json_string: &str) -> Result<BooleanNetworkModel, String> {
+        let json = json::parse(json_string).map_err(|e| format!("Invalid JSON: {}", e))?;
+        let version = json["version"]
+            .as_u64()
+            .ok_or_else(|| "Missing model format version.".to_string())?;
+        if version > JSON_FORMAT_VERSION as u64 {
+            return Err(format!(
+                "Unsupported model format version {} (expected at most {}).",
+                version, JSON_FORMAT_VERSION
+            ));
+        }
+        let file = ModelFile::from_json(&json)?;
+
+        let mut variable_data = HashMap::new();
+        let mut regulation_data: HashMap<u64, Vec<RegulationData>> = HashMap::new();
+        let mut names = HashSet::new();
+        for var in file.variables {
+            if var.id > file.id_counter {
+                return Err(format!("Variable ID {} exceeds the ID counter.", var.id));
+            }
+            if !names.insert(var.name.clone()) {
+                return Err(format!("Duplicate variable name `{}`.", var.name));
+            }
+            regulation_data.insert(var.id, Vec::new());
+            if variable_data.insert(var.id, var).is_some() {
+                return Err("Duplicate variable ID.".to_string());
+            }
+        }
+        for reg in file.regulations {
+            if !variable_data.contains_key(&reg.source) {
+                return Err(format!("Value {} is not a valid variable ID.", reg.source));
+            }
+            check_monotonicity(&reg.monotonicity)?;
+            let regulators = regulation_data
+                .get_mut(&reg.target)
+                .ok_or_else(|| format!("Value {} is not a valid variable ID.", reg.target))?;
+            if regulators.iter().any(|r| r.source == reg.source) {
+                return Err(format!(
+                    "Duplicate regulation {} -> {}.",
+                    reg.source, reg.target
+                ));
+            }
+            regulators.push(reg);
+        }
+        let metadata = &file.metadata;
+        let annotated = metadata.variables.keys().chain(metadata.regulations.keys());
+        let regulations = metadata
+            .regulations
+            .values()
+            .flat_map(|targets| targets.keys());
+        for id in annotated.chain(regulations) {
+            if !variable_data.contains_key(id) {
+                return Err(format!(
+                    "Invalid model: metadata of unknown variable ID {}.",
+                    id
+                ));
+            }
+        }
+        let unplaced = file
+            .unplaced
+            .into_iter()
+            .filter(|id| variable_data.contains_key(id))
+            .collect();
+
+        Ok(BooleanNetworkModel {
+            id_counter: file.id_counter,
+            variable_data,
+            regulation_data,
+            unplaced,
+            metadata: file.metadata,
+            history: EditHistory::default(),
+        })
+    }
+}
+
+impl ModelFile {
+    /// **(internal)** Write this file as a JSON object.
+    fn to_json(&self) -> JsonValue {
+        let variables = self.variables.iter().map(|var| {
+            object! {
+                "id": var.id,
+                "name": var.name.clone(),
+                "update_function": object! { "raw_string": var.update_function.raw_string.clone() },
+                "position": vec![var.position.0, var.position.1],
+            }
+        });
+        let regulations = self.regulations.iter().map(|reg| {
+            object! {
+                "source": reg.source,
+                "target": reg.target,
+                "observable": reg.observable,
+                "monotonicity": reg.monotonicity.clone(),
+            }
+        });
+        object! {
+            "version": self.version,
+            "id_counter": self.id_counter,
+            "variables": variables.collect::<Vec<_>>(),
+            "regulations": regulations.collect::<Vec<_>>(),
+            "unplaced": self.unplaced.clone(),
+            "metadata": metadata_to_json(&self.metadata),
+        }
+    }
+
+    /// **(internal)** Read a file from a JSON object. The `unplaced` and `metadata` fields
+    /// are optional.
+    fn from_json(json: &JsonValue) -> Result<ModelFile, String> {
+        let variables = list(json, "variables", |var| {
+            let position = &var["position"];
+            Ok(VariableData {
+                id: field(var, "id", JsonValue::as_u64)?,
+                name: field(var, "name", as_string)?,
+                update_function: UpdateFunctionData {
+                    raw_string: field(&var["update_function"], "raw_string", as_string)?,
+                },
+                position: match (position[0].as_f64(), position[1].as_f64()) {
+                    (Some(x), Some(y)) if position.len() == 2 => (x, y),
+                    _ => return Err("Invalid model: missing or invalid `position`.".to_string()),
+                },
+            })
+        })?;
+        let regulations = list(json, "regulations", |reg| {
+            Ok(RegulationData {
+                source: field(reg, "source", JsonValue::as_u64)?,
+                target: field(reg, "target", JsonValue::as_u64)?,
+                observable: field(reg, "observable", JsonValue::as_bool)?,
+                monotonicity: field(reg, "monotonicity", as_string)?,
+            })
+        })?;
+        let unplaced = if json["unplaced"].is_null() {
+            Vec::new()
+        } else {
+            list(json, "unplaced", |id| {
+                id.as_u64()
+                    .ok_or_else(|| "Invalid model: invalid `unplaced` ID.".to_string())
+            })?
+        };
+        let metadata = if json["metadata"].is_null() {
+            ModelMetadata::default()
+        } else {
+            metadata_from_json(&json["metadata"])?
+        };
+        Ok(ModelFile {
+            version: field(json, "version", JsonValue::as_u32)?,
+            id_counter: field(json, "id_counter", JsonValue::as_u64)?,
+            variables,
+            regulations,
+            unplaced,
+            metadata,
+        })
+    }
+}
+
+/// **(internal)** Write model metadata as a JSON object. Annotations are indexed by
+/// variable IDs written as strings.
+fn metadata_to_json(metadata: &ModelMetadata) -> JsonValue {
+    let mut variables = JsonValue::new_object();
+    for (id, annotations) in &metadata.variables {
+        variables[id.to_string().as_str()] = annotations_to_json(annotations);
+    }
+    let mut regulations = JsonValue::new_object();
+    for (source, targets) in &metadata.regulations {
+        let mut object = JsonValue::new_object();
+        for (target, annotations) in targets {
+            object[target.to_string().as_str()] = annotations_to_json(annotations);
+        }
+        regulations[source.to_string().as_str()] = object;
+    }
+    object! {
+        "name": metadata.name.clone(),
+        "description": metadata.description.clone(),
+        "variables": variables,
+        "regulations": regulations,
+    }
+}
+
+/// **(internal)** Read model metadata written by `metadata_to_json`.
+fn metadata_from_json(json: &JsonValue) -> Result<ModelMetadata, String> {
+    let mut variables = BTreeMap::new();
+    for (id, annotations) in id_map(&json["variables"])? {
+        variables.insert(id, annotations_from_json(annotations)?);
+    }
+    let mut regulations = BTreeMap::new();
+    for (source, targets) in id_map(&json["regulations"])? {
+        let mut annotations = BTreeMap::new();
+        for (target, value) in id_map(targets)? {
+            annotations.insert(target, annotations_from_json(value)?);
+        }
+        regulations.insert(source, annotations);
+    }
+    Ok(ModelMetadata {
+        name: field(json, "name", as_string)?,
+        description: field(json, "description", as_string)?,
+        variables,
+        regulations,
+    })
+}
+
+/// **(internal)** Write annotations as a JSON object with `notes`, `references` and `tags`.
+fn annotations_to_json(annotations: &Annotations) -> JsonValue {
+    object! {
+        "notes": annotations.notes.clone(),
+        "references": annotations.references.clone(),
+        "tags": annotations.tags.clone(),
+    }
+}
+
+/// **(internal)** Read annotations written by `annotations_to_json`.
+fn annotations_from_json(json: &JsonValue) -> Result<Annotations, String> {
+    let strings = |key: &str| {
+        list(json, key, |value| {
+            as_string(value).ok_or_else(|| format!("Invalid model: invalid `{}` value.", key))
+        })
+    };
+    Ok(Annotations {
+        notes: strings("notes")?,
+        references: strings("references")?,
+        tags: strings("tags")?,
+    })
+}
+
+/// **(internal)** Read the required `key` of a JSON object using `read`.
+fn field<'a, T>(
+    json: &'a JsonValue,
+    key: &str,
+    read: impl Fn(&'a JsonValue) -> Option<T>,
+) -> Result<T, String> {
+    read(&json[key]).ok_or_else(|| format!("Invalid model: missing or invalid `{}`.", key))
+}
+
+/// **(internal)** Read the required array `key` of a JSON object using `read` for every item.
+fn list<T>(
+    json: &JsonValue,
+    key: &str,
+    read: impl Fn(&JsonValue) -> Result<T, String>,
+) -> Result<Vec<T>, String> {
+    if !json[key].is_array() {
+        return Err(format!("Invalid model: missing or invalid `{}`.", key));
+    }
+    json[key].members().map(read).collect()
+}
+
+/// **(internal)** Read a JSON object whose keys are variable IDs.
+fn id_map(json: &JsonValue) -> Result<BTreeMap<u64, &JsonValue>, String> {
+    if !json.is_object() {
+        return Err("Invalid model: invalid metadata.".to_string());
+    }
+    json.entries()
+        .map(|(key, value)| match key.parse::<u64>() {
+            Ok(id) => Ok((id, value)),
+            Err(_) => Err(format!("Invalid model: `{}` is not a variable ID.", key)),
+        })
+        .collect()
+}
+
+/// **(internal)** Read a JSON string.
+fn as_string(json: &JsonValue) -> Option<String> {
+    json.as_str().map(|s| s.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boolean_network::BooleanNetworkModel;
+
+    #[test]
+    fn json_round_trip_preserves_ids() {
+        let json = r#"{ "version": 1, "id_counter": 7, "variables": [
+            { "id": 3, "name": "a", "update_function": { "raw_string": "!b" }, "position": [1.0, 2.0] },
+            { "id": 7, "name": "b", "update_function": { "raw_string": "" }, "position": [3.0, 4.0] }
+        ], "regulations": [
+            { "source": 7, "target": 3, "observable": true, "monotonicity": "-" }
+        ] }"#;
+        let model = BooleanNetworkModel::from_json(json).unwrap();
+        let copy = BooleanNetworkModel::from_json(&model.to_json()).unwrap();
+        assert_eq!(model.to_json(), copy.to_json());
+        assert_eq!("b", copy.get_variable_name(7).unwrap());
+        assert_eq!(7, copy.id_counter);
+
+        let future = json.replace("\"version\": 1", "\"version\": 2");
+        assert!(BooleanNetworkModel::from_json(&future).is_err());
+
+        // Metadata and unplaced variables are preserved as well.
+        let aeon = "a -| b\nb -> a\n#position:a:1,2\n#name:Test\n#note:a:Note\n#tag:a->b:x\n";
+        let model = BooleanNetworkModel::from_aeon(aeon).unwrap();
+        let copy = BooleanNetworkModel::from_json(&model.to_json()).unwrap();
+        assert_eq!(model.metadata, copy.metadata);
+        assert_eq!(model.unplaced, copy.unplaced);
+        assert_eq!(model.to_aeon(), copy.to_aeon());
+    }
+
+    #[test]
+    fn json_invalid_regulations_and_metadata() {
+        let json = r#"{ "version": 1, "id_counter": 1, "variables": [
+            { "id": 0, "name": "a", "update_function": { "raw_string": "" }, "position": [0, 0] },
+            { "id": 1, "name": "b", "update_function": { "raw_string": "" }, "position": [0, 0] }
+        ], "regulations": [
+            { "source": 0, "target": 1, "observable": true, "monotonicity": "+" }
+        ], "metadata": { "name": "", "description": "",
+            "variables": { "1": { "notes": ["Note"], "references": [], "tags": [] } },
+            "regulations": { "0": { "1": { "notes": [], "references": [], "tags": ["x"] } } } } }"#;
+        assert!(BooleanNetworkModel::from_json(json).is_ok());
+        let invalid = [
+            json.replace("\"monotonicity\": \"+\"", "\"monotonicity\": \"x\""),
+            json.replace("\"variables\": { \"1\"", "\"variables\": { \"2\""),
+            json.replace("{ \"0\": { \"1\"", "{ \"2\": { \"1\""),
+            json.replace("{ \"0\": { \"1\"", "{ \"0\": { \"2\""),
+        ];
+        for json in invalid {
+            assert!(BooleanNetworkModel::from_json(&json).is_err());
+        }
+    }
+}
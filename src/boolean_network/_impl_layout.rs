@@ -0,0 +1,104 @@
+use crate::boolean_network::{BooleanNetworkModel, EditCommand};
+use crate::graph_analysis::SignedGraph;
+use crate::layout::force_directed_layout;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Compute new positions of model variables using a force-directed layout algorithm.
+    ///
+    /// If `only_unplaced` is set, only variables whose position was inferred automatically
+    /// (because it was missing in the `.aeon` file, or the variable was created without
+    /// a position) and which were not moved since are placed. Otherwise, all variables are
+    /// placed. In `hierarchical` mode, variables are organized into layers based on the
+    /// strongly connected components of the regulatory graph.
+    ///
+    /// The whole layout is recorded as a single edit that can be reverted using `undo`.
+    /// Returns a list of `VariableData` objects with updated positions.
+    pub fn apply_layout(
+        &mut self,
+        only_unplaced: bool,
+        hierarchical: bool,
+    ) -> Result<JsValue, String> {
+        let changed = self.apply_layout_internal(only_unplaced, hierarchical)?;
+        let changed = changed
+            .into_iter()
+            .map(|id| &self.variable_data[&id])
+            .collect::<Vec<_>>();
+        Ok(serde_wasm_bindgen::to_value(&changed).unwrap())
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** Compute new variable positions (see `apply_layout`) and return the IDs
+    /// of all variables that were placed.
+    pub(crate) fn apply_layout_internal(
+        &mut self,
+        only_unplaced: bool,
+        hierarchical: bool,
+    ) -> Result<Vec<u64>, String> {
+        let graph = SignedGraph::from_model(self);
+        let fixed = (0..graph.num_vertices())
+            .map(|v| {
+                let id = graph.id(v);
+                if only_unplaced && !self.unplaced.contains(&id) {
+                    Some(self.variable_data[&id].position)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        let positions = force_directed_layout(&graph, &fixed, hierarchical);
+
+        let mut commands = Vec::new();
+        let mut changed = Vec::new();
+        for (v, position) in positions.into_iter().enumerate() {
+            if fixed[v].is_none() {
+                let id = graph.id(v);
+                commands.push(EditCommand::SetPosition {
+                    id,
+                    old: self.variable_data[&id].position,
+                    new: position,
+                    old_unplaced: self.unplaced.contains(&id),
+                    new_unplaced: false,
+                });
+                changed.push(id);
+            }
+        }
+        if !commands.is_empty() {
+            self.execute(EditCommand::Group(commands))?;
+        }
+
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boolean_network::BooleanNetworkModel;
+
+    #[test]
+    fn layout_of_unplaced_variables() {
+        let aeon = "a -> b\nb -> c\nc -| a\n#position:a:1,2\n";
+        let mut model = BooleanNetworkModel::from_aeon(aeon).unwrap();
+        let before = model.to_json();
+        assert_eq!(2, model.unplaced.len());
+
+        let mut changed = model.apply_layout_internal(true, false).unwrap();
+        changed.sort();
+        assert_eq!(vec![1, 2], changed);
+        assert_eq!("a", model.variable_data[&0].name);
+        assert_eq!((1.0, 2.0), model.variable_data[&0].position);
+        assert!(model.unplaced.is_empty());
+        assert_eq!(1, model.history_len());
+
+        // Once everything is placed, there is nothing to do.
+        assert!(model.apply_layout_internal(true, false).unwrap().is_empty());
+        assert_eq!(1, model.history_len());
+
+        assert_eq!(Ok(true), model.undo());
+        assert_eq!(before, model.to_json());
+        assert_eq!(0, model.history_len());
+    }
+}
@@ -0,0 +1,303 @@
+use crate::boolean_network::_impl_variables::rename_in_update_function;
+use crate::boolean_network::{BooleanNetworkModel, EditCommand, RegulationData};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+/// How to resolve variables with the same name when merging two models.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MergePolicy {
+    /// Merged variables are renamed to a fresh name.
+    Rename,
+    /// Merged variables are identified with the existing variables of the same name.
+    Unify,
+    /// The merge fails.
+    Fail,
+}
+
+/// Describes where a variable of the merged model ended up in the current model.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct MergedVariable {
+    merged_id: u64,
+    id: u64,
+    name: String,
+}
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Create a new model which only contains the variables with the given `ids` and
+    /// the regulations between them.
+    ///
+    /// Variables outside of `ids` which regulate the extracted variables are kept as
+    /// inputs: their own regulations are removed and their update functions are left
+    /// implicit. Variable IDs, positions and annotations are preserved.
+    pub fn extract_submodel(&self, ids: Vec<u64>) -> Result<BooleanNetworkModel, String> {
+        for id in &ids {
+            self.get_variable_data(*id)?;
+        }
+        let selected = ids.into_iter().collect::<HashSet<_>>();
+        let mut inputs = HashSet::new();
+        for id in &selected {
+            for reg in &self.regulation_data[id] {
+                if !selected.contains(&reg.source) {
+                    inputs.insert(reg.source);
+                }
+            }
+        }
+
+        let mut model = BooleanNetworkModel::new();
+        model.id_counter = self.id_counter;
+        for id in selected.iter().chain(inputs.iter()) {
+            let mut data = self.variable_data[id].clone();
+            let mut regulators = Vec::new();
+            if inputs.contains(id) {
+                data.update_function.raw_string = String::new();
+            } else {
+                regulators = self.regulation_data[id].clone();
+            }
+            model.variable_data.insert(*id, data);
+            model.regulation_data.insert(*id, regulators);
+            if self.unplaced.contains(id) {
+                model.unplaced.insert(*id);
+            }
+        }
+        let metadata = self.aeon_metadata();
+        model.metadata = model.metadata_from_aeon(&metadata);
+        // Annotations of regulations that were not extracted are dropped when exported,
+        // but it is cleaner not to carry them around at all.
+        for (source, targets) in model.metadata.regulations.iter_mut() {
+            targets.retain(|target, _| {
+                model.regulation_data[target]
+                    .iter()
+                    .any(|r| r.source == *source)
+            });
+        }
+        model
+            .metadata
+            .regulations
+            .retain(|_, targets| !targets.is_empty());
+        Ok(model)
+    }
+
+    /// Merge all variables and regulations of the `other` model into this model.
+    ///
+    /// The `policy` decides what happens when a variable of the same name exists in both
+    /// models:
+    ///  - `"rename"`: the merged variable is renamed (its update functions are rewritten);
+    ///  - `"unify"`: the two variables are identified. Missing regulations are added, and
+    ///    the update function is taken from `other` if this model has none. Two different
+    ///    update functions result in an error;
+    ///  - `"fail"`: an error is thrown.
+    ///
+    /// The positions of the merged variables are shifted by `(offset_x, offset_y)`. The whole
+    /// merge is a single edit that can be reverted using `undo`. Returns a list of objects
+    /// which map the `merged_id` of every variable of `other` to its `id` in this model.
+    pub fn merge(
+        &mut self,
+        other: &BooleanNetworkModel,
+        policy: &str,
+        offset_x: f64,
+        offset_y: f64,
+    ) -> Result<JsValue, String> {
+        let policy = match policy {
+            "rename" => MergePolicy::Rename,
+            "unify" => MergePolicy::Unify,
+            "fail" => MergePolicy::Fail,
+            _ => return Err(format!("Unknown merge policy `{}`.", policy)),
+        };
+        let merged = self.merge_model(other, policy, (offset_x, offset_y))?;
+        Ok(serde_wasm_bindgen::to_value(&merged).unwrap())
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** Merge the `other` model into this one. See `merge` for details.
+    pub(crate) fn merge_model(
+        &mut self,
+        other: &BooleanNetworkModel,
+        policy: MergePolicy,
+        offset: (f64, f64),
+    ) -> Result<Vec<MergedVariable>, String> {
+        let existing = self
+            .variable_data
+            .values()
+            .map(|var| (var.name.clone(), var.id))
+            .collect::<HashMap<_, _>>();
+        let mut used_names = existing
+            .keys()
+            .chain(other.variable_data.values().map(|var| &var.name))
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        // Decide the id and name of every merged variable.
+        let mut mapping = HashMap::new();
+        let mut renamed = Vec::new();
+        let mut next_id = self.id_counter;
+        for var in other.sorted_variables() {
+            match (existing.get(&var.name), policy) {
+                (Some(_), MergePolicy::Fail) => {
+                    return Err(format!("Variable `{}` already exists.", var.name));
+                }
+                (Some(id), MergePolicy::Unify) => {
+                    mapping.insert(var.id, (*id, var.name.clone()));
+                }
+                (collision, _) => {
+                    next_id += 1;
+                    let mut name = var.name.clone();
+                    if collision.is_some() {
+                        name = (2..)
+                            .map(|i| format!("{}_{}", var.name, i))
+                            .find(|candidate| !used_names.contains(candidate))
+                            .unwrap();
+                        used_names.insert(name.clone());
+                        renamed.push((var.name.clone(), name.clone()));
+                    }
+                    mapping.insert(var.id, (next_id, name));
+                }
+            }
+        }
+
+        let mut commands = Vec::new();
+        for var in other.sorted_variables() {
+            let (id, name) = mapping[&var.id].clone();
+            let mut function = var.update_function.raw_string.clone();
+            for (old_name, new_name) in &renamed {
+                function = rename_in_update_function(&function, old_name, new_name);
+            }
+            if let Some(current) = self.variable_data.get(&id) {
+                let current_function = current.update_function.raw_string.trim();
+                if function.trim().is_empty() || current_function == function.trim() {
+                    continue;
+                }
+                if !current_function.is_empty() {
+                    return Err(format!(
+                        "Variable `{}` has a different update function in each model.",
+                        name
+                    ));
+                }
+                commands.push(EditCommand::SetUpdateFunction {
+                    id,
+                    old: current.update_function.raw_string.clone(),
+                    new: function,
+                });
+            } else {
+                let mut data = var.clone();
+                data.id = id;
+                data.name = name;
+                data.update_function.raw_string = function;
+                data.position = (var.position.0 + offset.0, var.position.1 + offset.1);
+                commands.push(EditCommand::AddVariable {
+                    variable: data,
+                    unplaced: other.unplaced.contains(&var.id),
+                });
+            }
+        }
+        for reg in other.sorted_regulations() {
+            let (source, target) = (mapping[&reg.source].0, mapping[&reg.target].0);
+            if self.get_regulation_data(source, target).is_err() {
+                commands.push(EditCommand::AddRegulation(RegulationData {
+                    source,
+                    target,
+                    ..reg.clone()
+                }));
+            }
+        }
+
+        // Annotations of new variables and regulations are copied, annotations of unified
+        // variables and regulations are kept from this model.
+        let mut metadata = self.metadata.clone();
+        for (id, annotations) in &other.metadata.variables {
+            if let Some((new_id, _)) = mapping.get(id) {
+                metadata
+                    .variables
+                    .entry(*new_id)
+                    .or_insert_with(|| annotations.clone());
+            }
+        }
+        for (source, targets) in &other.metadata.regulations {
+            for (target, annotations) in targets {
+                if let (Some((source, _)), Some((target, _))) =
+                    (mapping.get(source), mapping.get(target))
+                {
+                    let targets = metadata.regulations.entry(*source).or_default();
+                    targets
+                        .entry(*target)
+                        .or_insert_with(|| annotations.clone());
+                }
+            }
+        }
+        if metadata != self.metadata {
+            commands.push(EditCommand::SetMetadata {
+                old: Box::new(self.metadata.clone()),
+                new: Box::new(metadata),
+            });
+        }
+
+        if !commands.is_empty() {
+            self.execute(EditCommand::Group(commands))?;
+        }
+        self.id_counter = next_id;
+
+        let mut result = mapping
+            .into_iter()
+            .map(|(merged_id, (id, name))| MergedVariable {
+                merged_id,
+                id,
+                name,
+            })
+            .collect::<Vec<_>>();
+        result.sort_by_key(|it| it.merged_id);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boolean_network::_impl_merge::MergePolicy;
+    use crate::boolean_network::BooleanNetworkModel;
+
+    #[test]
+    fn extract_and_merge_modules() {
+        // a -> b -| c -> a, with $b: a and $c: !b.
+        let json = r#"{ "version": 1, "id_counter": 2, "variables": [
+            { "id": 0, "name": "a", "update_function": { "raw_string": "" }, "position": [0.0, 0.0] },
+            { "id": 1, "name": "b", "update_function": { "raw_string": "a" }, "position": [1.0, 0.0] },
+            { "id": 2, "name": "c", "update_function": { "raw_string": "!b" }, "position": [2.0, 0.0] }
+        ], "regulations": [
+            { "source": 0, "target": 1, "observable": true, "monotonicity": "+" },
+            { "source": 1, "target": 2, "observable": true, "monotonicity": "-" },
+            { "source": 2, "target": 0, "observable": true, "monotonicity": "+" }
+        ] }"#;
+        let model = BooleanNetworkModel::from_json(json).unwrap();
+        let module = model.extract_submodel(vec![2]).unwrap();
+        assert_eq!(2, module.variable_data.len());
+        assert!(module.regulation_data[&1].is_empty());
+        assert!(module.variable_data[&1]
+            .update_function
+            .raw_string
+            .is_empty());
+
+        let mut merged = model.clone();
+        assert!(merged
+            .merge_model(&module, MergePolicy::Fail, (0.0, 0.0))
+            .is_err());
+        merged
+            .merge_model(&module, MergePolicy::Unify, (0.0, 0.0))
+            .unwrap();
+        assert_eq!(model.to_aeon(), merged.to_aeon());
+
+        let mapping = merged
+            .merge_model(&module, MergePolicy::Rename, (10.0, 0.0))
+            .unwrap();
+        assert_eq!(
+            vec!["b_2", "c_2"],
+            mapping.iter().map(|m| m.name.as_str()).collect::<Vec<_>>()
+        );
+        let c_2 = &merged.variable_data[&mapping[1].id];
+        assert_eq!("!b_2", c_2.update_function.raw_string);
+        assert_eq!(model.variable_data[&2].position.0 + 10.0, c_2.position.0);
+        assert_eq!(Ok(true), merged.undo());
+        assert_eq!(model.to_aeon(), merged.to_aeon());
+    }
+}
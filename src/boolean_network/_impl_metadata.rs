@@ -0,0 +1,168 @@
+use crate::boolean_network::{BooleanNetworkModel, EditCommand, ModelMetadata};
+use crate::conversions::{AeonMetadata, Annotations};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// The name of the model (empty if not set).
+    pub fn get_model_name(&self) -> String {
+        self.metadata.name.clone()
+    }
+
+    /// Change the name of the model.
+    pub fn set_model_name(&mut self, name: &str) -> Result<(), String> {
+        let mut metadata = self.metadata.clone();
+        metadata.name = name.to_string();
+        self.set_metadata(metadata)
+    }
+
+    /// The description of the model (empty if not set).
+    pub fn get_model_description(&self) -> String {
+        self.metadata.description.clone()
+    }
+
+    /// Change the description of the model.
+    pub fn set_model_description(&mut self, description: &str) -> Result<(), String> {
+        let mut metadata = self.metadata.clone();
+        metadata.description = description.to_string();
+        self.set_metadata(metadata)
+    }
+
+    /// Return the annotations of the variable with the given `id` as an object with
+    /// `notes`, `references` and `tags` lists of strings.
+    pub fn get_variable_annotations(&self, id: u64) -> Result<JsValue, String> {
+        self.get_variable_data(id)?;
+        let annotations = self.metadata.variables.get(&id).cloned();
+        Ok(serde_wasm_bindgen::to_value(&annotations.unwrap_or_default()).unwrap())
+    }
+
+    /// Replace the annotations of the variable with the given `id`. The `annotations`
+    /// object has the same structure as the one returned by `get_variable_annotations`.
+    pub fn set_variable_annotations(
+        &mut self,
+        id: u64,
+        annotations: JsValue,
+    ) -> Result<(), String> {
+        self.get_variable_data(id)?;
+        let annotations = parse_annotations(annotations)?;
+        let mut metadata = self.metadata.clone();
+        metadata.variables.insert(id, annotations);
+        metadata.variables.retain(|_, a| !is_empty(a));
+        self.set_metadata(metadata)
+    }
+
+    /// Return the annotations of the regulation between `source` and `target`.
+    pub fn get_regulation_annotations(&self, source: u64, target: u64) -> Result<JsValue, String> {
+        self.get_regulation_data(source, target)?;
+        let annotations = self
+            .metadata
+            .regulations
+            .get(&source)
+            .and_then(|targets| targets.get(&target))
+            .cloned();
+        Ok(serde_wasm_bindgen::to_value(&annotations.unwrap_or_default()).unwrap())
+    }
+
+    /// Replace the annotations of the regulation between `source` and `target`.
+    pub fn set_regulation_annotations(
+        &mut self,
+        source: u64,
+        target: u64,
+        annotations: JsValue,
+    ) -> Result<(), String> {
+        self.get_regulation_data(source, target)?;
+        let annotations = parse_annotations(annotations)?;
+        let mut metadata = self.metadata.clone();
+        let targets = metadata.regulations.entry(source).or_default();
+        targets.insert(target, annotations);
+        targets.retain(|_, a| !is_empty(a));
+        metadata
+            .regulations
+            .retain(|_, targets| !targets.is_empty());
+        self.set_metadata(metadata)
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** Replace the model metadata using an undoable edit.
+    fn set_metadata(&mut self, metadata: ModelMetadata) -> Result<(), String> {
+        if metadata != self.metadata {
+            self.execute(EditCommand::SetMetadata {
+                old: Box::new(self.metadata.clone()),
+                new: Box::new(metadata),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// **(internal)** The metadata of this model with variables identified by name. Only
+    /// annotations of existing variables and regulations are included.
+    pub(crate) fn aeon_metadata(&self) -> AeonMetadata {
+        let mut result = AeonMetadata {
+            name: self.metadata.name.clone(),
+            description: self.metadata.description.clone(),
+            ..Default::default()
+        };
+        for (id, annotations) in &self.metadata.variables {
+            if let Some(var) = self.variable_data.get(id) {
+                result
+                    .variables
+                    .insert(var.name.clone(), annotations.clone());
+            }
+        }
+        for (source, targets) in &self.metadata.regulations {
+            for (target, annotations) in targets {
+                if self.get_regulation_data(*source, *target).is_ok() {
+                    let key = (
+                        self.variable_data[source].name.clone(),
+                        self.variable_data[target].name.clone(),
+                    );
+                    result.regulations.insert(key, annotations.clone());
+                }
+            }
+        }
+        result
+    }
+
+    /// **(internal)** Convert name-based metadata into the metadata of this model.
+    /// Annotations of unknown variables are dropped.
+    pub(crate) fn metadata_from_aeon(&self, metadata: &AeonMetadata) -> ModelMetadata {
+        let ids = self
+            .variable_data
+            .values()
+            .map(|var| (var.name.as_str(), var.id))
+            .collect::<HashMap<_, _>>();
+        let mut result = ModelMetadata {
+            name: metadata.name.clone(),
+            description: metadata.description.clone(),
+            ..Default::default()
+        };
+        for (name, annotations) in &metadata.variables {
+            if let Some(id) = ids.get(name.as_str()) {
+                result.variables.insert(*id, annotations.clone());
+            }
+        }
+        for ((source, target), annotations) in &metadata.regulations {
+            if let (Some(source), Some(target)) =
+                (ids.get(source.as_str()), ids.get(target.as_str()))
+            {
+                let targets = result.regulations.entry(*source).or_default();
+                targets.insert(*target, annotations.clone());
+            }
+        }
+        result
+    }
+}
+
+/// **(internal)** Read an `Annotations` object provided by JavaScript.
+fn parse_annotations(annotations: JsValue) -> Result<Annotations, String> {
+    serde_wasm_bindgen::from_value(annotations)
+        .map_err(|e| format!("Invalid annotations object: {}", e))
+}
+
+/// **(internal)** True if there are no notes, references or tags.
+fn is_empty(annotations: &Annotations) -> bool {
+    annotations.notes.is_empty() && annotations.references.is_empty() && annotations.tags.is_empty()
+}
@@ -0,0 +1,201 @@
+use crate::boolean_network::{BooleanNetworkModel, EditCommand, RegulationData};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Return the `RegulationData` of the regulation between `source` and `target`.
+    pub fn get_regulation(&self, source: u64, target: u64) -> Result<JsValue, String> {
+        let data = self.get_regulation_data(source, target)?;
+        Ok(serde_wasm_bindgen::to_value(data).unwrap())
+    }
+
+    /// Add a new regulation from `source` to `target`. The `monotonicity` must be one
+    /// of `"+"`, `"-"` or `""` (unspecified).
+    ///
+    /// Returns the `RegulationData` of the new regulation. Fails if one of the variables does
+    /// not exist, or if the regulation is already present in the model.
+    pub fn add_regulation(
+        &mut self,
+        source: u64,
+        target: u64,
+        observable: bool,
+        monotonicity: &str,
+    ) -> Result<JsValue, String> {
+        let data = self.add_regulation_internal(source, target, observable, monotonicity)?;
+        Ok(serde_wasm_bindgen::to_value(&data).unwrap())
+    }
+
+    /// Remove the regulation from `source` to `target`.
+    ///
+    /// Returns the `RegulationData` of the removed regulation.
+    pub fn remove_regulation(&mut self, source: u64, target: u64) -> Result<JsValue, String> {
+        let data = self.remove_regulation_internal(source, target)?;
+        Ok(serde_wasm_bindgen::to_value(&data).unwrap())
+    }
+
+    /// Change the monotonicity of the regulation from `source` to `target`. The `monotonicity`
+    /// must be one of `"+"`, `"-"` or `""` (unspecified).
+    ///
+    /// Returns the updated `RegulationData`.
+    pub fn set_regulation_monotonicity(
+        &mut self,
+        source: u64,
+        target: u64,
+        monotonicity: &str,
+    ) -> Result<JsValue, String> {
+        check_monotonicity(monotonicity)?;
+        let data = self.update_regulation(source, target, |reg| {
+            reg.monotonicity = monotonicity.to_string();
+        })?;
+        Ok(serde_wasm_bindgen::to_value(&data).unwrap())
+    }
+
+    /// Change the observability of the regulation from `source` to `target`.
+    ///
+    /// Returns the updated `RegulationData`.
+    pub fn set_regulation_observable(
+        &mut self,
+        source: u64,
+        target: u64,
+        observable: bool,
+    ) -> Result<JsValue, String> {
+        let data = self.update_regulation(source, target, |reg| reg.observable = observable)?;
+        Ok(serde_wasm_bindgen::to_value(&data).unwrap())
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** Add a new regulation (see `add_regulation`) and return its data.
+    pub(crate) fn add_regulation_internal(
+        &mut self,
+        source: u64,
+        target: u64,
+        observable: bool,
+        monotonicity: &str,
+    ) -> Result<RegulationData, String> {
+        self.get_variable_data(source)?;
+        self.get_variable_data(target)?;
+        check_monotonicity(monotonicity)?;
+        if self.get_regulation_data(source, target).is_ok() {
+            return Err(format!(
+                "Regulation {} -> {} already exists.",
+                source, target
+            ));
+        }
+
+        let data = RegulationData {
+            source,
+            target,
+            observable,
+            monotonicity: monotonicity.to_string(),
+        };
+
+        self.execute(EditCommand::AddRegulation(data.clone()))?;
+        Ok(data)
+    }
+
+    /// **(internal)** Find the regulation from `source` to `target`, or an error if such
+    /// regulation does not exist.
+    pub(crate) fn get_regulation_data(
+        &self,
+        source: u64,
+        target: u64,
+    ) -> Result<&RegulationData, String> {
+        self.get_variable_data(source)?;
+        self.get_variable_data(target)?;
+        self.regulation_data[&target]
+            .iter()
+            .find(|it| it.source == source)
+            .ok_or_else(|| format!("Regulation {} -> {} does not exist.", source, target))
+    }
+
+    /// **(internal)** Remove the regulation from `source` to `target` and return its data.
+    pub(crate) fn remove_regulation_internal(
+        &mut self,
+        source: u64,
+        target: u64,
+    ) -> Result<RegulationData, String> {
+        let data = self.get_regulation_data(source, target)?.clone();
+        self.execute(EditCommand::RemoveRegulation(data.clone()))?;
+        Ok(data)
+    }
+
+    /// **(internal)** Modify the regulation from `source` to `target` using `update` and
+    /// return the new data.
+    pub(crate) fn update_regulation<F: FnOnce(&mut RegulationData)>(
+        &mut self,
+        source: u64,
+        target: u64,
+        update: F,
+    ) -> Result<RegulationData, String> {
+        let old = self.get_regulation_data(source, target)?.clone();
+        let mut new = old.clone();
+        update(&mut new);
+        self.execute(EditCommand::UpdateRegulation {
+            old,
+            new: new.clone(),
+        })?;
+        Ok(new)
+    }
+}
+
+/// **(internal)** Check that the given string is a valid monotonicity value.
+pub(crate) fn check_monotonicity(monotonicity: &str) -> Result<(), String> {
+    match monotonicity {
+        "+" | "-" | "" => Ok(()),
+        _ => Err(format!(
+            "Value `{}` is not a valid monotonicity. Expected `+`, `-` or empty string.",
+            monotonicity
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boolean_network::BooleanNetworkModel;
+
+    #[test]
+    fn edit_regulations_with_undo() {
+        let mut model = BooleanNetworkModel::from_aeon("a -> b\n$b: a\n").unwrap();
+        let (a, b) = (0, 1);
+
+        // Duplicate regulations, unknown variables and invalid monotonicity are rejected.
+        assert!(model.add_regulation_internal(a, b, true, "-").is_err());
+        assert!(model.add_regulation_internal(a, 7, true, "+").is_err());
+        assert!(model.add_regulation_internal(7, a, true, "+").is_err());
+        assert!(model.add_regulation_internal(b, a, true, "x").is_err());
+        assert!(model.set_regulation_monotonicity(a, b, "x").is_err());
+        assert!(model.remove_regulation_internal(b, a).is_err());
+        assert!(model.remove_regulation_internal(a, 7).is_err());
+        assert!(model.update_regulation(7, b, |_| ()).is_err());
+        assert_eq!(0, model.history_len());
+
+        let added = model.add_regulation_internal(b, a, false, "-").unwrap();
+        assert_eq!(
+            (b, a, false, "-"),
+            (
+                added.source,
+                added.target,
+                added.observable,
+                added.monotonicity.as_str()
+            )
+        );
+        let updated = model
+            .update_regulation(a, b, |reg| reg.monotonicity = String::new())
+            .unwrap();
+        assert_eq!("", updated.monotonicity);
+        assert_eq!("", model.get_regulation_data(a, b).unwrap().monotonicity);
+        model.remove_regulation_internal(b, a).unwrap();
+        assert!(model.get_regulation_data(b, a).is_err());
+        assert_eq!(3, model.history_len());
+
+        assert_eq!(Ok(true), model.undo());
+        assert_eq!("-", model.get_regulation_data(b, a).unwrap().monotonicity);
+        assert_eq!(Ok(true), model.undo());
+        assert_eq!("+", model.get_regulation_data(a, b).unwrap().monotonicity);
+        assert_eq!(Ok(true), model.undo());
+        assert!(model.get_regulation_data(b, a).is_err());
+        assert_eq!(1, model.regulation_data.values().flatten().count());
+    }
+}
@@ -0,0 +1,210 @@
+use crate::boolean_network::_fn_parser::UpdateFunctionError;
+use crate::boolean_network::_fn_tokenizer::is_name_char;
+use crate::boolean_network::BooleanNetworkModel;
+use biodivine_lib_param_bn::{BooleanNetwork, Monotonicity, RegulatoryGraph};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+/// One problem which prevents a `BooleanNetworkModel` from being converted into
+/// a `BooleanNetwork`.
+///
+/// If the problem concerns a specific variable, `variable` is its ID. If it concerns
+/// a regulation, `variable` is the target and `regulator` is the source of the regulation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct ModelProblem {
+    pub variable: Option<u64>,
+    pub regulator: Option<u64>,
+    pub message: String,
+    pub function_error: Option<UpdateFunctionError>,
+}
+
+impl ModelProblem {
+    /// A problem that is not related to any particular part of the model.
+    pub fn global(message: String) -> ModelProblem {
+        ModelProblem {
+            variable: None,
+            regulator: None,
+            message,
+            function_error: None,
+        }
+    }
+
+    /// Convert a list of problems into a JS array.
+    pub fn list_to_js(problems: &[ModelProblem]) -> JsValue {
+        serde_wasm_bindgen::to_value(problems).unwrap()
+    }
+}
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Return a list of problems which prevent this model from being analysed. If the list
+    /// is empty, the model can be used in `ComputationResult.compute_model`.
+    ///
+    /// Each problem has a `message`, and optionally the `variable` ID and `regulator` ID
+    /// of the affected variable or regulation. For invalid update functions,
+    /// `function_error` contains the same error object as `set_update_function`.
+    pub fn get_problems(&self) -> JsValue {
+        let problems = match self.to_boolean_network() {
+            Ok(_) => Vec::new(),
+            Err(problems) => problems,
+        };
+        ModelProblem::list_to_js(&problems)
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** Build a `BooleanNetwork` from this model. Variables are ordered by ID.
+    ///
+    /// If the model is not valid, the result contains all problems that were found.
+    pub(crate) fn to_boolean_network(&self) -> Result<BooleanNetwork, Vec<ModelProblem>> {
+        let variables = self.sorted_variables();
+
+        let mut problems = Vec::new();
+        let mut names = HashSet::new();
+        for var in &variables {
+            let message = if var.name.is_empty() || !var.name.chars().all(is_name_char) {
+                format!("Value `{}` is not a valid variable name.", var.name)
+            } else if !names.insert(var.name.as_str()) {
+                format!("Variable name `{}` is used more than once.", var.name)
+            } else {
+                continue;
+            };
+            problems.push(ModelProblem {
+                variable: Some(var.id),
+                regulator: None,
+                message,
+                function_error: None,
+            });
+        }
+        if !problems.is_empty() {
+            return Err(problems);
+        }
+
+        let mut graph =
+            RegulatoryGraph::new(variables.iter().map(|var| var.name.clone()).collect());
+        for var in &variables {
+            for reg in &self.regulation_data[&var.id] {
+                let monotonicity = match reg.monotonicity.as_str() {
+                    "+" => Some(Monotonicity::Activation),
+                    "-" => Some(Monotonicity::Inhibition),
+                    _ => None,
+                };
+                let source = &self.variable_data[&reg.source].name;
+                let result = graph.add_regulation(source, &var.name, reg.observable, monotonicity);
+                if let Err(message) = result {
+                    problems.push(ModelProblem {
+                        variable: Some(var.id),
+                        regulator: Some(reg.source),
+                        message,
+                        function_error: None,
+                    });
+                }
+            }
+        }
+
+        let variable_ids: HashMap<&str, u64> = variables
+            .iter()
+            .map(|var| (var.name.as_str(), var.id))
+            .collect();
+        let mut parameters: Vec<(String, usize)> = Vec::new();
+        for var in &variables {
+            match self.validate_update_function(var.id, &var.update_function.raw_string) {
+                Ok(symbols) => {
+                    for parameter in symbols.parameter_arity(&variable_ids) {
+                        if !parameters.contains(&parameter) {
+                            parameters.push(parameter);
+                        }
+                    }
+                }
+                Err(error) => problems.push(ModelProblem {
+                    variable: Some(var.id),
+                    regulator: None,
+                    message: format!(
+                        "Invalid update function of `{}`: {}",
+                        var.name, error.message
+                    ),
+                    function_error: Some(error),
+                }),
+            }
+        }
+        if !problems.is_empty() {
+            return Err(problems);
+        }
+
+        let mut network = BooleanNetwork::new(graph);
+        for (name, arity) in parameters {
+            if let Err(message) = network.add_parameter(&name, arity as u32) {
+                problems.push(ModelProblem::global(message));
+            }
+        }
+        for var in &variables {
+            let function = var.update_function.raw_string.trim();
+            if function.is_empty() {
+                continue;
+            }
+            if let Err(message) = network.add_string_update_function(&var.name, function) {
+                problems.push(ModelProblem {
+                    variable: Some(var.id),
+                    regulator: None,
+                    message,
+                    function_error: None,
+                });
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(network)
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boolean_network::BooleanNetworkModel;
+
+    #[test]
+    fn report_model_problems() {
+        let mut model = BooleanNetworkModel::from_aeon("a -> b\nb -| a\n$a: !b\n$b: a\n").unwrap();
+        assert_eq!(2, model.to_boolean_network().unwrap().num_vars());
+        let id = |model: &BooleanNetworkModel, name: &str| {
+            model
+                .variable_data
+                .values()
+                .find(|var| var.name == name)
+                .unwrap()
+                .id
+        };
+        let (a, b) = (id(&model, "a"), id(&model, "b"));
+
+        // Every invalid update function is reported.
+        model
+            .variable_data
+            .get_mut(&a)
+            .unwrap()
+            .update_function
+            .raw_string = "(!b".into();
+        model
+            .variable_data
+            .get_mut(&b)
+            .unwrap()
+            .update_function
+            .raw_string = "a |".into();
+        let problems = model.to_boolean_network().unwrap_err();
+        assert_eq!(2, problems.len());
+        assert_eq!(Some(a), problems[0].variable);
+        assert!(problems[0]
+            .message
+            .starts_with("Invalid update function of `a`"));
+        assert!(problems.iter().all(|p| p.function_error.is_some()));
+
+        // Invalid names are reported before anything else.
+        model.variable_data.get_mut(&a).unwrap().name = "b".into();
+        let problems = model.to_boolean_network().unwrap_err();
+        assert_eq!(1, problems.len());
+        assert!(problems[0].message.contains("used more than once"));
+    }
+}
@@ -0,0 +1,274 @@
+use crate::boolean_network::_fn_parser::{
+    parse_update_function, FnErrorReason, FnSymbols, UpdateFunctionError,
+};
+use crate::boolean_network::{BooleanNetworkModel, EditCommand};
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Set the update function of the variable with the given `id`. An empty string
+    /// makes the update function implicit (unspecified).
+    ///
+    /// The function is first validated against the variables, regulations and parameters
+    /// of the model, and it is only stored if it is valid. Returns the updated `VariableData`.
+    ///
+    /// If the `id` is not valid, a `string` error is thrown. If the function is not valid,
+    /// the error is an object with `offset` (character offset into the function string),
+    /// `token` (the offending token), `reason` (one of `syntax`, `unknown-variable`,
+    /// `missing-regulation` or `arity-mismatch`) and a human-readable `message`.
+    ///
+    /// Names which are not variables are accepted as parameters without arguments, but they
+    /// are reported by `get_update_function_warnings`.
+    pub fn set_update_function(&mut self, id: u64, function: &str) -> Result<JsValue, JsValue> {
+        self.get_variable_data(id)?;
+        if let Err(error) = self.validate_update_function(id, function) {
+            return Err(serde_wasm_bindgen::to_value(&error).unwrap());
+        }
+        let old = self.variable_data[&id].update_function.raw_string.clone();
+        self.execute(EditCommand::SetUpdateFunction {
+            id,
+            old,
+            new: function.to_string(),
+        })?;
+        Ok(self.get_variable(id)?)
+    }
+
+    /// Return a list of warnings about the update function of the variable with the given
+    /// `id`. Warnings have the same structure as the errors of `set_update_function`.
+    ///
+    /// A warning (with reason `unknown-variable`) is reported for every bare name which is
+    /// not a variable. Such a name is a valid parameter without arguments, but it is often
+    /// a misspelled variable name.
+    pub fn get_update_function_warnings(&self, id: u64) -> Result<JsValue, String> {
+        let warnings = self.update_function_warnings(id)?;
+        Ok(serde_wasm_bindgen::to_value(&warnings).unwrap())
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** Check that `function` is a valid update function for the variable `id`.
+    ///
+    /// Parameter arity is checked against the parameters used in the update functions
+    /// of the remaining variables (invalid functions are ignored in this check).
+    pub(crate) fn validate_update_function(
+        &self,
+        id: u64,
+        function: &str,
+    ) -> Result<FnSymbols, UpdateFunctionError> {
+        let symbols = parse_update_function(function)?;
+
+        let variables: HashMap<&str, u64> = self
+            .variable_data
+            .values()
+            .map(|var| (var.name.as_str(), var.id))
+            .collect();
+        let regulators: HashSet<u64> = self.regulation_data[&id]
+            .iter()
+            .map(|reg| reg.source)
+            .collect();
+        let target_name = &self.variable_data[&id].name;
+
+        let check_regulation = |offset: usize, name: &str, source: u64| {
+            if regulators.contains(&source) {
+                Ok(())
+            } else {
+                Err(UpdateFunctionError {
+                    offset,
+                    token: name.to_string(),
+                    reason: FnErrorReason::MissingRegulation,
+                    message: format!(
+                        "Variable `{}` is used, but there is no regulation `{}` -> `{}`.",
+                        name, name, target_name
+                    ),
+                })
+            }
+        };
+
+        let mut arity: HashMap<String, usize> = HashMap::new();
+        for var in self.variable_data.values() {
+            if var.id == id {
+                continue;
+            }
+            if let Ok(other) = parse_update_function(&var.update_function.raw_string) {
+                for (name, a) in other.parameter_arity(&variables) {
+                    arity.entry(name).or_insert(a);
+                }
+            }
+        }
+
+        for (offset, name) in &symbols.names {
+            if let Some(source) = variables.get(name.as_str()) {
+                check_regulation(*offset, name, *source)?;
+            }
+        }
+        for (offset, name) in &symbols.arguments {
+            if let Some(source) = variables.get(name.as_str()) {
+                check_regulation(*offset, name, *source)?;
+            } else {
+                return Err(UpdateFunctionError {
+                    offset: *offset,
+                    token: name.clone(),
+                    reason: FnErrorReason::UnknownVariable,
+                    message: format!("Parameter argument `{}` is not a variable.", name),
+                });
+            }
+        }
+
+        let mut uses: Vec<(usize, &String, usize)> = symbols
+            .parameters
+            .iter()
+            .map(|(offset, name, a)| (*offset, name, *a))
+            .collect();
+        uses.extend(
+            symbols
+                .names
+                .iter()
+                .filter(|(_, name)| !variables.contains_key(name.as_str()))
+                .map(|(offset, name)| (*offset, name, 0)),
+        );
+        uses.sort();
+        for (offset, name, a) in uses {
+            if variables.contains_key(name.as_str()) {
+                return Err(UpdateFunctionError {
+                    offset,
+                    token: name.clone(),
+                    reason: FnErrorReason::Syntax,
+                    message: format!("Variable `{}` cannot be used as a parameter.", name),
+                });
+            }
+            let expected = *arity.entry(name.clone()).or_insert(a);
+            if expected != a {
+                return Err(UpdateFunctionError {
+                    offset,
+                    token: name.clone(),
+                    reason: FnErrorReason::ArityMismatch,
+                    message: format!(
+                        "Parameter `{}` is used with {} argument(s), but it has arity {}.",
+                        name, a, expected
+                    ),
+                });
+            }
+        }
+
+        Ok(symbols)
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** List the warnings about the update function of the variable `id`
+    /// (see `get_update_function_warnings`). Invalid functions have no warnings.
+    pub(crate) fn update_function_warnings(
+        &self,
+        id: u64,
+    ) -> Result<Vec<UpdateFunctionError>, String> {
+        let function = &self.get_variable_data(id)?.update_function.raw_string;
+        let symbols = match parse_update_function(function) {
+            Ok(symbols) => symbols,
+            Err(_) => return Ok(Vec::new()),
+        };
+        let variables: HashSet<&str> = self
+            .variable_data
+            .values()
+            .map(|var| var.name.as_str())
+            .collect();
+        let warnings = symbols
+            .names
+            .into_iter()
+            .filter(|(_, name)| !variables.contains(name.as_str()))
+            .map(|(offset, name)| UpdateFunctionError {
+                offset,
+                message: format!(
+                    "Name `{}` is not a variable, so it is used as a parameter without arguments.",
+                    name
+                ),
+                token: name,
+                reason: FnErrorReason::UnknownVariable,
+            })
+            .collect();
+        Ok(warnings)
+    }
+}
+
+impl FnSymbols {
+    /// **(internal)** List all parameters used in the function, together with their arity.
+    /// Bare names that are not `variables` are considered to be zero-arity parameters.
+    pub(crate) fn parameter_arity(&self, variables: &HashMap<&str, u64>) -> Vec<(String, usize)> {
+        let mut result: Vec<(String, usize)> = self
+            .parameters
+            .iter()
+            .map(|(_, name, arity)| (name.clone(), *arity))
+            .collect();
+        for (_, name) in &self.names {
+            if !variables.contains_key(name.as_str()) {
+                result.push((name.clone(), 0));
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boolean_network::_fn_parser::FnErrorReason;
+    use crate::boolean_network::BooleanNetworkModel;
+
+    #[test]
+    fn update_function_model_errors() {
+        let aeon = "a -> b\nb -> a\nc -> b\n$a: f(b)\n";
+        let model = BooleanNetworkModel::from_aeon(aeon).unwrap();
+        let reason = |id, function| {
+            model
+                .validate_update_function(id, function)
+                .map(|_| ())
+                .map_err(|e| (e.reason, e.offset, e.token))
+        };
+        assert_eq!(Ok(()), reason(1, "a & !c | f(c)"));
+        assert_eq!(
+            Err((FnErrorReason::MissingRegulation, 4, "c".to_string())),
+            reason(0, "b & c")
+        );
+        assert_eq!(
+            Err((FnErrorReason::MissingRegulation, 2, "c".to_string())),
+            reason(0, "g(c)")
+        );
+        assert_eq!(
+            Err((FnErrorReason::UnknownVariable, 2, "x".to_string())),
+            reason(1, "f(x)")
+        );
+        assert_eq!(
+            Err((FnErrorReason::ArityMismatch, 0, "f".to_string())),
+            reason(1, "f(a, c)")
+        );
+        assert_eq!(
+            Err((FnErrorReason::ArityMismatch, 4, "f".to_string())),
+            reason(1, "a | f")
+        );
+        assert_eq!(
+            Err((FnErrorReason::Syntax, 0, "a".to_string())),
+            reason(1, "a(c)")
+        );
+    }
+
+    #[test]
+    fn unknown_names_are_warnings() {
+        let mut model = BooleanNetworkModel::from_aeon("a -> b\n").unwrap();
+        let function = &mut model.variable_data.get_mut(&1).unwrap().update_function;
+        function.raw_string = "a | ab & p".to_string();
+        let warnings = model.update_function_warnings(1).unwrap();
+        let tokens = warnings
+            .iter()
+            .map(|w| (w.offset, w.token.as_str(), w.reason))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (4, "ab", FnErrorReason::UnknownVariable),
+                (9, "p", FnErrorReason::UnknownVariable)
+            ],
+            tokens
+        );
+        assert!(model.update_function_warnings(0).unwrap().is_empty());
+        assert!(model.update_function_warnings(2).is_err());
+    }
+}
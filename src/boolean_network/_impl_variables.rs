@@ -0,0 +1,195 @@
+use crate::boolean_network::_fn_tokenizer::{tokenize_update_function, variable_references};
+use crate::boolean_network::{BooleanNetworkModel, EditCommand, RegulationData, VariableData};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+/// Summary of the changes caused by `BooleanNetworkModel::remove_variable`.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct VariableRemoval {
+    variable: VariableData,
+    removed_regulations: Vec<RegulationData>,
+    // IDs of variables whose update function now references the removed variable.
+    invalid_functions: Vec<u64>,
+}
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Remove the variable with the given `id`, together with all its incoming and outgoing
+    /// regulations.
+    ///
+    /// Update functions are not modified, but the result lists the IDs of all variables
+    /// whose update function still references the removed variable (and is thus invalid).
+    pub fn remove_variable(&mut self, id: u64) -> Result<JsValue, String> {
+        let variable = self.get_variable_data(id)?.clone();
+        let mut removed_regulations = self.regulation_data[&id].clone();
+        for (target, regulators) in &self.regulation_data {
+            if *target != id {
+                removed_regulations.extend(regulators.iter().filter(|r| r.source == id).cloned());
+            }
+        }
+        self.execute(EditCommand::RemoveVariable {
+            variable: variable.clone(),
+            regulations: removed_regulations.clone(),
+            unplaced: self.unplaced.contains(&id),
+        })?;
+
+        let mut invalid_functions = self
+            .variable_data
+            .values()
+            .filter(|var| {
+                let tokens = tokenize_update_function(&var.update_function.raw_string);
+                !variable_references(&tokens, &variable.name).is_empty()
+            })
+            .map(|var| var.id)
+            .collect::<Vec<_>>();
+        invalid_functions.sort();
+
+        let result = VariableRemoval {
+            variable,
+            removed_regulations,
+            invalid_functions,
+        };
+        Ok(serde_wasm_bindgen::to_value(&result).unwrap())
+    }
+
+    /// Rename the variable with the given `id`. All references to the variable in the
+    /// update functions of the model are rewritten to use the new name.
+    ///
+    /// Returns a list of `VariableData` objects that were modified (the renamed variable
+    /// and all variables with a rewritten update function). Renaming a variable to its
+    /// current name does nothing and returns an empty list.
+    ///
+    /// The name must consist of letters, digits and `_`, and it cannot be `true` or `false`.
+    pub fn rename_variable(&mut self, id: u64, name: &str) -> Result<JsValue, String> {
+        let changed = self.rename_variable_internal(id, name)?;
+        let changed = changed
+            .into_iter()
+            .map(|id| &self.variable_data[&id])
+            .collect::<Vec<_>>();
+        Ok(serde_wasm_bindgen::to_value(&changed).unwrap())
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** Rename the variable with the given `id` (see `rename_variable`) and
+    /// return the sorted IDs of all modified variables.
+    pub(crate) fn rename_variable_internal(
+        &mut self,
+        id: u64,
+        name: &str,
+    ) -> Result<Vec<u64>, String> {
+        let old_name = self.get_variable_name(id)?;
+        if name == old_name {
+            return Ok(Vec::new());
+        }
+        self.check_new_variable_name(name)?;
+
+        let mut changed = vec![id];
+        let mut commands = vec![EditCommand::SetName {
+            id,
+            old: old_name.clone(),
+            new: name.to_string(),
+        }];
+        for var in self.sorted_variables() {
+            let function = &var.update_function.raw_string;
+            let renamed = rename_in_update_function(function, &old_name, name);
+            if renamed != *function {
+                commands.push(EditCommand::SetUpdateFunction {
+                    id: var.id,
+                    old: function.clone(),
+                    new: renamed,
+                });
+                if var.id != id {
+                    changed.push(var.id);
+                }
+            }
+        }
+        self.execute(EditCommand::Group(commands))?;
+
+        changed.sort();
+        Ok(changed)
+    }
+
+    /// **(internal)** Check that `name` can be used as the name of a new variable. It must
+    /// consist of letters, digits and `_`, it cannot be `true` or `false`, and no other
+    /// variable can have the same name.
+    pub(crate) fn check_new_variable_name(&self, name: &str) -> Result<(), String> {
+        let is_valid = !name.is_empty()
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && name != "true"
+            && name != "false";
+        if !is_valid {
+            return Err(format!("Value `{}` is not a valid variable name.", name));
+        }
+        if self.variable_data.values().any(|var| var.name == name) {
+            return Err(format!("Variable `{}` already exists.", name));
+        }
+        Ok(())
+    }
+}
+
+/// **(internal)** Replace every reference to the `old_name` variable in the given update
+/// function with `new_name`. The rest of the function string (including whitespace)
+/// is preserved.
+pub(crate) fn rename_in_update_function(function: &str, old_name: &str, new_name: &str) -> String {
+    let tokens = tokenize_update_function(function);
+    let references = variable_references(&tokens, old_name);
+    if references.is_empty() {
+        return function.to_string();
+    }
+
+    let chars: Vec<char> = function.chars().collect();
+    let mut result = String::new();
+    let mut last = 0;
+    for i in references {
+        let (offset, token) = &tokens[i];
+        result.extend(&chars[last..*offset]);
+        result.push_str(new_name);
+        last = offset + token.char_len();
+    }
+    result.extend(&chars[last..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boolean_network::_impl_variables::rename_in_update_function;
+    use crate::boolean_network::BooleanNetworkModel;
+
+    #[test]
+    fn rename_preserves_parameters_and_formatting() {
+        let function = "a & !ab | f(a,  b) ^ a(b)";
+        assert_eq!(
+            "xy & !ab | f(xy,  b) ^ a(b)",
+            rename_in_update_function(function, "a", "xy")
+        );
+        assert_eq!(function, rename_in_update_function(function, "c", "d"));
+    }
+
+    #[test]
+    fn rename_variable_validation() {
+        let mut model = BooleanNetworkModel::from_aeon("a -> b\nb -| a\n$a: !b\n$b: a\n").unwrap();
+        assert_eq!(Ok(Vec::new()), model.rename_variable_internal(0, "a"));
+        assert_eq!(0, model.history_len());
+        for name in ["", "true", "false", "a{1}", "a-b", "b"] {
+            assert!(model.rename_variable_internal(0, name).is_err());
+        }
+        assert_eq!(Ok(vec![0, 1]), model.rename_variable_internal(0, "x_1"));
+        assert_eq!("x_1", model.variable_data[&1].update_function.raw_string);
+    }
+
+    #[test]
+    fn add_variable_validation() {
+        let mut model = BooleanNetworkModel::from_aeon("a -> v_3\n").unwrap();
+        for name in ["a", "a b", "true", ""] {
+            assert!(model
+                .add_variable_internal(Some(name.to_string()), None)
+                .is_err());
+        }
+        assert_eq!(0, model.history_len());
+        assert_eq!(Ok(3), model.add_variable_internal(None, None));
+        assert_eq!("v_4", model.variable_data[&3].name);
+        assert!(model.to_boolean_network().is_ok());
+    }
+}
@@ -0,0 +1,347 @@
+use crate::conversions::{read_metadata, Annotations};
+use crate::read_layout;
+use crate::utils::infer_new_position;
+use biodivine_lib_param_bn::{BooleanNetwork, Monotonicity};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+pub(crate) use _impl_to_network::ModelProblem;
+
+/// **(internal)** A parser which validates update function expressions.
+mod _fn_parser;
+/// **(internal)** A simple tokenizer for update function expressions.
+mod _fn_tokenizer;
+/// **(internal)** Export of the model into `.aeon` and other supported formats.
+mod _impl_aeon_export;
+/// **(internal)** Structural comparison of two models.
+mod _impl_diff;
+/// **(internal)** Implementation of reversible edit commands and the undo/redo history.
+mod _impl_edit_history;
+/// **(internal)** Export of the regulatory graph into `.dot`, `.graphml` and Cytoscape JSON.
+mod _impl_graph_export;
+/// **(internal)** Import of the regulatory graph from `.graphml` and Cytoscape JSON.
+mod _impl_graph_import;
+/// **(internal)** Versioned JSON serialization of the complete model.
+mod _impl_json;
+/// **(internal)** Automatic layout of model variables.
+mod _impl_layout;
+/// **(internal)** Extraction of sub-models and merging of models.
+mod _impl_merge;
+/// **(internal)** Methods for reading and editing model name, description and annotations.
+mod _impl_metadata;
+/// **(internal)** Methods for adding, removing and modifying regulations of the model.
+mod _impl_regulations;
+/// **(internal)** Conversion of the model into a `BooleanNetwork` suitable for analysis.
+mod _impl_to_network;
+/// **(internal)** Methods for validating and modifying update functions.
+mod _impl_update_functions;
+/// **(internal)** Methods for removing and renaming model variables.
+mod _impl_variables;
+
+/// A structure that is exported to JavaScript and provides a more JS-friendly abstraction of
+/// a "Boolean network".
+///
+/// **Work in progress! Most of the actually useful APIs are still missing.**
+///
+/// **Note that you have to explicitly call `free` to release a `BooleanNetworkModel` object
+/// on the JS side once it is no longer needed.**
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct BooleanNetworkModel {
+    id_counter: u64,
+    variable_data: HashMap<u64, VariableData>,
+    // Variable to a list of regulators.
+    regulation_data: HashMap<u64, Vec<RegulationData>>,
+    // Variables with an automatically inferred position that were not placed yet.
+    unplaced: HashSet<u64>,
+    metadata: ModelMetadata,
+    history: EditHistory,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct VariableData {
+    pub(crate) id: u64,
+    pub(crate) name: String,
+    pub(crate) update_function: UpdateFunctionData,
+    pub(crate) position: (f64, f64),
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct RegulationData {
+    pub(crate) source: u64,
+    pub(crate) target: u64,
+    pub(crate) observable: bool,
+    // Currently allowed values: "+", "-", ""
+    pub(crate) monotonicity: String,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct UpdateFunctionData {
+    raw_string: String,
+}
+
+/// Name, description and annotations of a `BooleanNetworkModel`. Variable annotations are
+/// indexed by variable ID, regulation annotations by source and then target ID.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ModelMetadata {
+    name: String,
+    description: String,
+    variables: BTreeMap<u64, Annotations>,
+    regulations: BTreeMap<u64, BTreeMap<u64, Annotations>>,
+}
+
+/// A single reversible modification of a `BooleanNetworkModel`.
+///
+/// Every command stores enough information to be applied again (redo) or reverted (undo),
+/// including whether the affected variable is unplaced (its position was inferred).
+#[derive(Clone)]
+pub(crate) enum EditCommand {
+    AddVariable {
+        variable: VariableData,
+        unplaced: bool,
+    },
+    RemoveVariable {
+        variable: VariableData,
+        // All incoming and outgoing regulations of the removed variable.
+        regulations: Vec<RegulationData>,
+        unplaced: bool,
+    },
+    SetName {
+        id: u64,
+        old: String,
+        new: String,
+    },
+    SetPosition {
+        id: u64,
+        old: (f64, f64),
+        new: (f64, f64),
+        old_unplaced: bool,
+        new_unplaced: bool,
+    },
+    SetUpdateFunction {
+        id: u64,
+        old: String,
+        new: String,
+    },
+    AddRegulation(RegulationData),
+    RemoveRegulation(RegulationData),
+    UpdateRegulation {
+        old: RegulationData,
+        new: RegulationData,
+    },
+    SetMetadata {
+        old: Box<ModelMetadata>,
+        new: Box<ModelMetadata>,
+    },
+    Group(Vec<EditCommand>),
+}
+
+/// The undo/redo history of a `BooleanNetworkModel`.
+///
+/// Consecutive edits of the same property (e.g. moving the same variable, or typing into
+/// the same update function) are merged into a single command, unless a checkpoint is
+/// created in between. Edits performed while a group is open are undone together.
+#[derive(Clone, Default)]
+pub(crate) struct EditHistory {
+    undo_stack: Vec<EditCommand>,
+    redo_stack: Vec<EditCommand>,
+    open_group: Option<Vec<EditCommand>>,
+    can_merge: bool,
+}
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    pub fn new() -> BooleanNetworkModel {
+        BooleanNetworkModel {
+            id_counter: 0,
+            variable_data: HashMap::new(),
+            regulation_data: HashMap::new(),
+            unplaced: HashSet::new(),
+            metadata: ModelMetadata::default(),
+            history: EditHistory::default(),
+        }
+    }
+
+    pub fn from_aeon(aeon_string: &str) -> Result<BooleanNetworkModel, String> {
+        let bn = BooleanNetwork::try_from(aeon_string)?;
+        let layout = read_layout(aeon_string);
+
+        let mut variable_data = HashMap::new();
+        let mut regulation_data = HashMap::new();
+        let mut unplaced = HashSet::new();
+
+        for var in bn.variables() {
+            let id = var.to_index() as u64;
+            let fn_string = match bn.get_update_function(var).as_ref() {
+                Some(fun) => fun.to_string(&bn),
+                None => String::new(),
+            };
+            let position = layout
+                .get(bn.get_variable_name(var))
+                .cloned()
+                .unwrap_or_else(|| {
+                    unplaced.insert(id);
+                    infer_new_position(&variable_data)
+                });
+
+            let data = VariableData {
+                id,
+                name: bn.get_variable_name(var).clone(),
+                update_function: UpdateFunctionData {
+                    raw_string: fn_string,
+                },
+                position,
+            };
+
+            variable_data.insert(id, data);
+            regulation_data.insert(id, Vec::new());
+        }
+
+        for reg in bn.as_graph().regulations() {
+            let data = RegulationData {
+                source: reg.regulator.to_index() as u64,
+                target: reg.target.to_index() as u64,
+                observable: reg.is_observable(),
+                monotonicity: match reg.monotonicity {
+                    None => String::new(),
+                    Some(Monotonicity::Activation) => "+".to_string(),
+                    Some(Monotonicity::Inhibition) => "-".to_string(),
+                },
+            };
+
+            let target = reg.target.to_index() as u64;
+            regulation_data.get_mut(&target).unwrap().push(data);
+        }
+
+        let mut model = BooleanNetworkModel {
+            id_counter: bn.num_vars() as u64,
+            variable_data,
+            regulation_data,
+            unplaced,
+            metadata: ModelMetadata::default(),
+            history: EditHistory::default(),
+        };
+        model.metadata = model.metadata_from_aeon(&read_metadata(aeon_string));
+        Ok(model)
+    }
+
+    /// Returns true if the model has zero variables.
+    pub fn is_empty(&self) -> bool {
+        self.variable_data.is_empty()
+    }
+
+    /// Return the name of the variable with the given integer `id`.
+    pub fn get_variable_name(&self, id: u64) -> Result<String, String> {
+        self.get_variable_data(id).map(|var| var.name.clone())
+    }
+
+    /// Add a new variable with the given `name` (or `v_{id}` if not given) at the given
+    /// `[x, y]` position (or an inferred position if not given). The name must satisfy
+    /// the same rules as in `rename_variable`.
+    ///
+    /// Returns the `VariableData` of the new variable.
+    pub fn add_variable(
+        &mut self,
+        name: Option<String>,
+        position: Option<Vec<f64>>,
+    ) -> Result<JsValue, String> {
+        let id = self.add_variable_internal(name, position)?;
+        self.get_variable(id)
+    }
+
+    /// Return the `VariableData` of the variable with the given `id`.
+    pub fn get_variable(&self, id: u64) -> Result<JsValue, String> {
+        let data = self.get_variable_data(id)?;
+        Ok(serde_wasm_bindgen::to_value(data).unwrap())
+    }
+
+    /// Return a list of `VariableData` objects of all variables, sorted by ID.
+    pub fn get_variables(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.sorted_variables()).unwrap()
+    }
+
+    /// Return a list of `RegulationData` objects of all regulations, sorted by target
+    /// and source ID.
+    pub fn get_regulations(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.sorted_regulations()).unwrap()
+    }
+
+    /// Move the variable with the given `id` to a new position.
+    ///
+    /// Returns the updated `VariableData`.
+    pub fn set_variable_position(&mut self, id: u64, x: f64, y: f64) -> Result<JsValue, String> {
+        let old = self.get_variable_data(id)?.position;
+        self.execute(EditCommand::SetPosition {
+            id,
+            old,
+            new: (x, y),
+            old_unplaced: self.unplaced.contains(&id),
+            new_unplaced: false,
+        })?;
+        self.get_variable(id)
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** Add a new variable (see `add_variable`) and return its ID. If the
+    /// variable cannot be added, the model is not modified.
+    pub(crate) fn add_variable_internal(
+        &mut self,
+        name: Option<String>,
+        position: Option<Vec<f64>>,
+    ) -> Result<u64, String> {
+        let id = self.id_counter + 1;
+
+        let name = match name {
+            Some(x) => x,
+            // Skip default names which are already taken by other variables.
+            None => (id..)
+                .map(|i| format!("v_{}", i))
+                .find(|name| self.variable_data.values().all(|var| var.name != *name))
+                .unwrap(),
+        };
+        self.check_new_variable_name(&name)?;
+
+        let (position, unplaced) = match position {
+            Some(x) if x.len() == 2 => ((x[0], x[1]), false),
+            Some(x) => {
+                return Err(format!(
+                    "Expected a position with 2 coordinates, but {} were given.",
+                    x.len()
+                ))
+            }
+            None => (infer_new_position(&self.variable_data), true),
+        };
+
+        let update_function = UpdateFunctionData {
+            raw_string: String::new(),
+        };
+
+        let data = VariableData {
+            id,
+            name,
+            position,
+            update_function,
+        };
+
+        self.execute(EditCommand::AddVariable {
+            variable: data,
+            unplaced,
+        })?;
+        self.id_counter = id;
+        Ok(id)
+    }
+
+    /// **(internal)** Get the data of the variable with the given `id`, or an error if
+    /// such variable does not exist.
+    pub(crate) fn get_variable_data(&self, id: u64) -> Result<&VariableData, String> {
+        self.variable_data
+            .get(&id)
+            .ok_or_else(|| format!("Value {} is not a valid variable ID.", id))
+    }
+}
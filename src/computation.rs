@@ -9,6 +9,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::bdt::Bdt;
+use crate::boolean_network::{BooleanNetworkModel, ModelProblem};
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::JsValue;
 
@@ -54,6 +55,75 @@ impl ComputationResult {
         on_progress: &js_sys::Function,
     ) -> Result<ComputationResult, String> {
         let bn = BooleanNetwork::try_from(model)?;
+        Self::compute_network(bn, on_progress)
+    }
+
+    /// Same as `compute`, but the network is given as a `BooleanNetworkModel` instead of
+    /// an `.aeon` string.
+    ///
+    /// If the model is not valid, the error is a list of problems, the same as the one
+    /// returned by `BooleanNetworkModel.get_problems`.
+    pub fn compute_model(
+        model: &BooleanNetworkModel,
+        on_progress: &js_sys::Function,
+    ) -> Result<ComputationResult, JsValue> {
+        let bn = model
+            .to_boolean_network()
+            .map_err(|problems| ModelProblem::list_to_js(&problems))?;
+        // A valid model can only fail here when the network is too large to be encoded.
+        Self::compute_network(bn, on_progress)
+            .map_err(|message| ModelProblem::list_to_js(&[ModelProblem::global(message)]))
+    }
+
+    pub fn get_results(&self) -> JsValue {
+        Self::get_results_internal(self.elapsed, &self.task, &self.classifier)
+    }
+
+    pub fn get_tree_data(&self) -> JsValue {
+        let mut serialized_data = HashMap::new();
+        for (k, v) in self.classifier.export_result() {
+            serialized_data.insert(k, v.into_bdd().to_bytes());
+        }
+        let data = TreeData {
+            network: self.network.to_string(),
+            data: serialized_data,
+        };
+        serde_wasm_bindgen::to_value(&data).unwrap()
+    }
+
+    fn get_results_internal(
+        elapsed: Duration,
+        task: &GraphTaskContext,
+        classifier: &Classifier,
+    ) -> JsValue {
+        let data = classifier.export_result();
+
+        let mut data_result = Vec::new();
+        for (k, v) in &data {
+            data_result.push(ResultsSummaryRow {
+                cardinality: v.approx_cardinality(),
+                class: k.get_str_vector(),
+            })
+        }
+
+        let result = ResultsSummary {
+            is_finished: task.progress.is_finished(),
+            progress: task.progress.get_percent_string(),
+            elapsed: elapsed.as_millis() as u64,
+            data: data_result,
+        };
+
+        serde_wasm_bindgen::to_value(&result).unwrap()
+    }
+}
+
+impl ComputationResult {
+    /// **(internal)** Run the attractor analysis of an already constructed network.
+    /// Shared by `compute` and `compute_model`.
+    fn compute_network(
+        bn: BooleanNetwork,
+        on_progress: &js_sys::Function,
+    ) -> Result<ComputationResult, String> {
         let graph = SymbolicAsyncGraph::new(&bn)?;
         let classifier = Classifier::new(&graph);
 
@@ -106,47 +176,6 @@ impl ComputationResult {
             elapsed,
         })
     }
-
-    pub fn get_results(&self) -> JsValue {
-        Self::get_results_internal(self.elapsed, &self.task, &self.classifier)
-    }
-
-    pub fn get_tree_data(&self) -> JsValue {
-        let mut serialized_data = HashMap::new();
-        for (k, v) in self.classifier.export_result() {
-            serialized_data.insert(k, v.into_bdd().to_bytes());
-        }
-        let data = TreeData {
-            network: self.network.to_string(),
-            data: serialized_data,
-        };
-        serde_wasm_bindgen::to_value(&data).unwrap()
-    }
-
-    fn get_results_internal(
-        elapsed: Duration,
-        task: &GraphTaskContext,
-        classifier: &Classifier,
-    ) -> JsValue {
-        let data = classifier.export_result();
-
-        let mut data_result = Vec::new();
-        for (k, v) in &data {
-            data_result.push(ResultsSummaryRow {
-                cardinality: v.approx_cardinality(),
-                class: k.get_str_vector(),
-            })
-        }
-
-        let result = ResultsSummary {
-            is_finished: task.progress.is_finished(),
-            progress: task.progress.get_percent_string(),
-            elapsed: elapsed.as_millis() as u64,
-            data: data_result,
-        };
-
-        serde_wasm_bindgen::to_value(&result).unwrap()
-    }
 }
 
 #[wasm_bindgen]
@@ -0,0 +1,190 @@
+use biodivine_lib_param_bn::{BooleanNetwork, Monotonicity, RegulatoryGraph};
+use regex::Regex;
+use std::collections::HashMap;
+
+/// The prefix of `.bnet` comments which carry `.aeon` specific information.
+const AEON_COMMENT: &str = "# aeon:";
+
+/// **(internal)** A regulation declared in a `# aeon:regulation:` comment, given as
+/// `(source, target, observable, monotonicity)`.
+pub(crate) type DeclaredRegulation = (String, String, bool, Option<Monotonicity>);
+
+/// **(internal)** The `# aeon:` comments of a `.bnet` file: declared variables without
+/// an update function, declared regulations, and the remaining `.aeon` metadata comments.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct BnetAnnotations {
+    pub(crate) variables: Vec<String>,
+    pub(crate) regulations: Vec<DeclaredRegulation>,
+    pub(crate) comments: String,
+}
+
+/// Write the regulations of the `network` and the metadata comments of the original
+/// `aeon_string` (layout, name, description and annotations) as `# aeon:` comments, which
+/// other tools ignore. Variables without an update function (which have no line in
+/// the `.bnet` file) are written as `# aeon:variable:` comments.
+pub(crate) fn write_bnet_annotations(network: &BooleanNetwork, aeon_string: &str) -> String {
+    let mut result = String::new();
+    for var in network.variables() {
+        if network.get_update_function(var).is_none() {
+            let name = network.get_variable_name(var);
+            result += format!("{}variable:{}\n", AEON_COMMENT, name).as_str();
+        }
+    }
+    for reg in network.as_graph().regulations() {
+        let arrow = match reg.get_monotonicity() {
+            Some(Monotonicity::Activation) => "->",
+            Some(Monotonicity::Inhibition) => "-|",
+            None => "-?",
+        };
+        let observable = if reg.is_observable() { "" } else { "?" };
+        result += format!(
+            "{}regulation:{} {}{} {}\n",
+            AEON_COMMENT,
+            network.get_variable_name(reg.get_regulator()),
+            arrow,
+            observable,
+            network.get_variable_name(reg.get_target()),
+        )
+        .as_str();
+    }
+    let metadata_re =
+        Regex::new(r"^\s*#(?P<line>(position|name|description|note|reference|tag):.*)$").unwrap();
+    for line in aeon_string.lines() {
+        if let Some(captures) = metadata_re.captures(line) {
+            result += format!("{}{}\n", AEON_COMMENT, &captures["line"]).as_str();
+        }
+    }
+    result
+}
+
+/// Read the `# aeon:` comments of a `.bnet` file. The remaining comments are translated
+/// back into `.aeon` metadata comments.
+pub(crate) fn read_bnet_annotations(bnet_string: &str) -> BnetAnnotations {
+    let variable_re = Regex::new(r"^variable:(?P<name>[a-zA-Z0-9_{}]+)\s*$").unwrap();
+    let regulation_re = Regex::new(
+        r"^regulation:(?P<source>[a-zA-Z0-9_{}]+)\s*-(?P<arrow>[>|?])(?P<observable>\??)\s*(?P<target>[a-zA-Z0-9_{}]+)\s*$",
+    )
+    .unwrap();
+    let mut annotations = BnetAnnotations::default();
+    for line in bnet_string.lines() {
+        let line = match line.trim().strip_prefix(AEON_COMMENT) {
+            Some(line) => line,
+            None => continue,
+        };
+        if let Some(captures) = variable_re.captures(line) {
+            annotations.variables.push(captures["name"].to_string());
+        } else if let Some(captures) = regulation_re.captures(line) {
+            let monotonicity = match &captures["arrow"] {
+                ">" => Some(Monotonicity::Activation),
+                "|" => Some(Monotonicity::Inhibition),
+                _ => None,
+            };
+            annotations.regulations.push((
+                captures["source"].to_string(),
+                captures["target"].to_string(),
+                captures["observable"].is_empty(),
+                monotonicity,
+            ));
+        } else if !line.starts_with("regulation:") && !line.starts_with("variable:") {
+            annotations.comments += format!("#{}\n", line).as_str();
+        }
+    }
+    annotations
+}
+
+/// Create a copy of the `network` where the properties of its regulations are replaced
+/// by the declared regulations of the `annotations`. Declared variables and regulations
+/// which are not in the network are added.
+pub(crate) fn declare_regulations(
+    network: &BooleanNetwork,
+    annotations: &BnetAnnotations,
+) -> Result<BooleanNetwork, String> {
+    let mut regulations = HashMap::new();
+    let mut order = Vec::new();
+    for reg in network.as_graph().regulations() {
+        let key = (
+            network.get_variable_name(reg.get_regulator()).clone(),
+            network.get_variable_name(reg.get_target()).clone(),
+        );
+        regulations.insert(key.clone(), (reg.is_observable(), reg.get_monotonicity()));
+        order.push(key);
+    }
+    for (source, target, observable, monotonicity) in &annotations.regulations {
+        let key = (source.clone(), target.clone());
+        if regulations
+            .insert(key.clone(), (*observable, *monotonicity))
+            .is_none()
+        {
+            order.push(key);
+        }
+    }
+
+    let mut names = network
+        .variables()
+        .map(|v| network.get_variable_name(v).clone())
+        .collect::<Vec<_>>();
+    for name in &annotations.variables {
+        if !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+    let mut graph = RegulatoryGraph::new(names);
+    for (source, target) in order {
+        let (observable, monotonicity) = regulations[&(source.clone(), target.clone())];
+        graph.add_regulation(&source, &target, observable, monotonicity)?;
+    }
+    let mut result = BooleanNetwork::new(graph);
+    for v in network.variables() {
+        if let Some(function) = network.get_update_function(v) {
+            let function = function.to_string(network);
+            result.add_string_update_function(network.get_variable_name(v), &function)?;
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversions::_impl_bnet::read_bnet_annotations;
+    use crate::Conversions;
+    use biodivine_lib_param_bn::{BooleanNetwork, Monotonicity};
+
+    #[test]
+    fn read_aeon_comments_in_bnet() {
+        let bnet = "targets, factors\na, !b\nb, a\n# aeon:regulation:b -| a\n\
+            # aeon:regulation:a -?? b\n# aeon:variable:c\n# aeon:position:a:1,2\n\
+            # aeon:name:Toggle\n";
+        let annotations = read_bnet_annotations(bnet);
+        assert_eq!(
+            vec![
+                (
+                    "b".to_string(),
+                    "a".to_string(),
+                    true,
+                    Some(Monotonicity::Inhibition)
+                ),
+                ("a".to_string(), "b".to_string(), false, None),
+            ],
+            annotations.regulations
+        );
+        assert_eq!(vec!["c".to_string()], annotations.variables);
+        assert_eq!("#position:a:1,2\n#name:Toggle\n", annotations.comments);
+    }
+
+    #[test]
+    fn bnet_round_trip() {
+        // `c` is a free input without an update function, so it has no line in `.bnet`.
+        let aeon = "a -> b\nb -| a\nc -?? a\n$a: !b\n$b: a\n#name:Toggle\n\
+            #position:a:1,2\n#position:b:3,4\n#position:c:5,6\n";
+        let bnet = Conversions::aeon_to_bnet(aeon).unwrap();
+        assert!(bnet.contains("# aeon:variable:c\n"));
+        let restored = Conversions::bnet_to_aeon(&bnet).unwrap();
+        assert_eq!(
+            BooleanNetwork::try_from(aeon).unwrap(),
+            BooleanNetwork::try_from(restored.as_str()).unwrap()
+        );
+        for line in aeon.lines().filter(|line| line.starts_with('#')) {
+            assert!(restored.contains(line));
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use crate::conversions::ConversionError;
+use crate::conversions::ConversionErrorKind::{Syntax, UnknownVariable, UnsupportedFeature};
+use crate::static_constraints::tighten_regulations;
+use crate::Conversions;
+use biodivine_lib_param_bn::{BinaryOp, BooleanNetwork, FnUpdate, RegulatoryGraph};
+use regex::{Captures, Regex};
+use std::collections::HashSet;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Function names that only appear in temporal BoolNet networks.
+const TEMPORAL_PREDICATES: [&str; 8] = [
+    "all", "any", "maj", "sumgt", "sumlt", "timeis", "timegt", "timelt",
+];
+
+#[wasm_bindgen]
+impl Conversions {
+    /// Convert a Boolean network model in the R BoolNet format (`targets, factors` table)
+    /// into a model encoded as an `.aeon` string.
+    ///
+    /// The `NOT`, `AND` and `OR` keywords are accepted alongside `!`, `&` and `|`. Since
+    /// BoolNet does not declare regulation monotonicity, it is inferred from the update
+    /// functions. Probabilistic (with a `probabilities` column) and temporal BoolNet
+    /// networks are not supported.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    pub fn boolnet_to_aeon(boolnet_string: &str) -> Result<String, ConversionError> {
+        let error = ConversionError::in_format("boolnet", Syntax, boolnet_string);
+        let unknown = ConversionError::in_format("boolnet", UnknownVariable, boolnet_string);
+        let functions = parse_boolnet(boolnet_string)?;
+        let names = functions
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        let known = names.iter().collect::<HashSet<_>>();
+        let name_re = Regex::new(r"[A-Za-z0-9_]+").unwrap();
+        let mut graph = RegulatoryGraph::new(names.clone());
+        for (target, function) in &functions {
+            let mut regulators = name_re
+                .find_iter(function)
+                .map(|m| m.as_str().to_string())
+                .filter(|name| name != "true" && name != "false")
+                .collect::<Vec<_>>();
+            regulators.sort();
+            regulators.dedup();
+            for regulator in regulators {
+                if !known.contains(&regulator) {
+                    return Err(unknown(format!(
+                        "Unknown variable `{}` in the update function of `{}`.",
+                        regulator, target
+                    )));
+                }
+                graph
+                    .add_regulation(&regulator, target, false, None)
+                    .map_err(&error)?;
+            }
+        }
+        let mut network = BooleanNetwork::new(graph);
+        for (target, function) in &functions {
+            network
+                .add_string_update_function(target, function)
+                .map_err(&error)?;
+        }
+        Ok(tighten_regulations(&network).map_err(&error)?.to_string())
+    }
+
+    /// Convert a Boolean network model encoded as an `.aeon` string into the R BoolNet
+    /// format.
+    ///
+    /// All update functions must be fully specified (no implicit functions or
+    /// uninterpreted parameters). Operators which BoolNet does not support (`^`, `=>`,
+    /// `<=>`) are rewritten using `!`, `&` and `|`. Layout and regulation monotonicity
+    /// are discarded.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    pub fn aeon_to_boolnet(aeon_string: &str) -> Result<String, ConversionError> {
+        let error = ConversionError::in_format("aeon", Syntax, aeon_string);
+        let unsupported = ConversionError::in_format("aeon", UnsupportedFeature, aeon_string);
+        let network = BooleanNetwork::try_from(aeon_string).map_err(error)?;
+        let mut result = String::from("targets, factors\n");
+        for var in network.variables() {
+            let name = network.get_variable_name(var);
+            let function = match network.get_update_function(var) {
+                Some(function) => function,
+                None => {
+                    return Err(unsupported(format!(
+                        "Variable `{}` has an implicit update function, which cannot be \
+                        represented in BoolNet.",
+                        name
+                    )))
+                }
+            };
+            let function = boolnet_function(&network, function).map_err(&unsupported)?;
+            result += format!("{}, {}\n", name, function).as_str();
+        }
+        Ok(result)
+    }
+}
+
+/// **(internal)** Read the `(target, factors)` pairs of a BoolNet file. Factors are
+/// translated into the aeon update function syntax.
+pub(crate) fn parse_boolnet(
+    boolnet_string: &str,
+) -> Result<Vec<(String, String)>, ConversionError> {
+    let error = |kind, message: String, line: usize| {
+        ConversionError::new("boolnet", kind, message).at(line, None)
+    };
+    let word_re = Regex::new(r"[A-Za-z0-9_.]+").unwrap();
+    let temporal_re = Regex::new(r"([A-Za-z]+)\s*\(").unwrap();
+    let translate_word = |captures: &Captures| match captures[0].to_lowercase().as_str() {
+        "not" => "!".to_string(),
+        "and" => "&".to_string(),
+        "or" => "|".to_string(),
+        "true" | "1" => "true".to_string(),
+        "false" | "0" => "false".to_string(),
+        _ => captures[0].replace('.', "_"),
+    };
+
+    let mut lines = boolnet_string
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let (header_line, header) = match lines.next() {
+        Some((line_number, header)) => (
+            line_number,
+            header
+                .split(',')
+                .map(|column| column.trim().to_lowercase())
+                .collect::<Vec<_>>(),
+        ),
+        None => {
+            let message = "Empty BoolNet file.".to_string();
+            return Err(ConversionError::new("boolnet", Syntax, message));
+        }
+    };
+    if header.len() > 2 && header[2].starts_with("prob") {
+        let message = "Probabilistic BoolNet networks are not supported.".to_string();
+        return Err(error(UnsupportedFeature, message, header_line));
+    }
+    if header.len() != 2 || header[0] != "targets" || header[1] != "factors" {
+        let message = "Expected a `targets, factors` header in the BoolNet file.".to_string();
+        return Err(error(Syntax, message, header_line));
+    }
+
+    let mut result: Vec<(String, String)> = Vec::new();
+    for (line_number, line) in lines {
+        let (target, factors) = match line.split_once(',') {
+            Some(pair) => pair,
+            None => {
+                let message = format!("Line {}: expected `target, factors`.", line_number);
+                return Err(error(Syntax, message, line_number));
+            }
+        };
+        if factors.contains(',') {
+            let message = format!(
+                "Line {}: unexpected extra column (probabilistic BoolNet networks are \
+                not supported).",
+                line_number
+            );
+            return Err(error(UnsupportedFeature, message, line_number));
+        }
+        let is_temporal = factors.contains('[')
+            || temporal_re
+                .captures_iter(factors)
+                .any(|c| TEMPORAL_PREDICATES.contains(&c[1].to_lowercase().as_str()));
+        if is_temporal {
+            let message = format!(
+                "Line {}: temporal BoolNet networks are not supported.",
+                line_number
+            );
+            return Err(error(UnsupportedFeature, message, line_number));
+        }
+        let target = target.trim().replace('.', "_");
+        if target.is_empty()
+            || !target
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            let message = format!("Line {}: invalid target `{}`.", line_number, target);
+            return Err(error(Syntax, message, line_number).with_identifier(&target));
+        }
+        if result.iter().any(|(name, _)| *name == target) {
+            let message = format!("Line {}: duplicate target `{}`.", line_number, target);
+            return Err(error(Syntax, message, line_number).with_identifier(&target));
+        }
+        let function = word_re.replace_all(factors.trim(), translate_word);
+        result.push((target, function.to_string()));
+    }
+    Ok(result)
+}
+
+/// **(internal)** Write an update function using the operators supported by BoolNet.
+fn boolnet_function(network: &BooleanNetwork, function: &FnUpdate) -> Result<String, String> {
+    Ok(match function {
+        FnUpdate::Const(value) => if *value { "1" } else { "0" }.to_string(),
+        FnUpdate::Var(var) => network.get_variable_name(*var).clone(),
+        FnUpdate::Param(param, _) => {
+            return Err(format!(
+                "Parameter `{}` cannot be represented in BoolNet.",
+                network.get_parameter(*param).get_name()
+            ))
+        }
+        FnUpdate::Not(inner) => format!("!{}", boolnet_function(network, inner)?),
+        FnUpdate::Binary(op, left, right) => {
+            let l = boolnet_function(network, left)?;
+            let r = boolnet_function(network, right)?;
+            match op {
+                BinaryOp::And => format!("({} & {})", l, r),
+                BinaryOp::Or => format!("({} | {})", l, r),
+                BinaryOp::Imp => format!("(!{} | {})", l, r),
+                BinaryOp::Iff => format!("(({} & {}) | (!{} & !{}))", l, r, l, r),
+                BinaryOp::Xor => format!("(({} & !{}) | (!{} & {}))", l, r, l, r),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversions::_impl_boolnet::parse_boolnet;
+    use crate::conversions::ConversionErrorKind::UnsupportedFeature;
+
+    #[test]
+    fn parse_boolnet_targets_and_factors() {
+        let boolnet = "# comment\ntargets, factors\nCycD, CycD\nRb.1, (NOT CycD AND 1) | !Rb.1\n";
+        let expected = vec![
+            ("CycD".to_string(), "CycD".to_string()),
+            ("Rb_1".to_string(), "(! CycD & true) | !Rb_1".to_string()),
+        ];
+        assert_eq!(Ok(expected), parse_boolnet(boolnet));
+
+        let probabilistic = "targets, factors, probabilities\nA, A, 1\n";
+        let error = parse_boolnet(probabilistic).unwrap_err();
+        assert!(error.message.contains("Probabilistic"));
+        assert_eq!(UnsupportedFeature, error.kind);
+        let temporal = "targets, factors\nA, B[-2]\nB, timeis(3)\n";
+        let error = parse_boolnet(temporal).unwrap_err();
+        assert!(error.message.contains("temporal"));
+        assert_eq!(Some(2), error.line);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::conversions::{ConversionError, ConversionErrorKind};
+use regex::Regex;
+use roxmltree::Node;
+use std::fmt::{Display, Formatter};
+
+impl ConversionError {
+    /// Create a new error of the given `kind` without a known location.
+    pub fn new(format: &str, kind: ConversionErrorKind, message: String) -> ConversionError {
+        ConversionError {
+            format: format.to_string(),
+            kind,
+            message,
+            line: None,
+            column: None,
+            identifier: None,
+        }
+    }
+
+    /// Set the `line` and `column` of this error.
+    pub fn at(mut self, line: usize, column: Option<usize>) -> ConversionError {
+        self.line = Some(line as u32);
+        self.column = column.map(|c| c as u32);
+        self
+    }
+
+    /// Set the offending `identifier` of this error.
+    pub fn with_identifier(mut self, identifier: &str) -> ConversionError {
+        self.identifier = Some(identifier.to_string());
+        self
+    }
+
+    /// Set the `line` and `column` of this error to the start of the given XML `node`.
+    pub(crate) fn at_node(self, node: Node) -> ConversionError {
+        let position = node.document().text_pos_at(node.range().start);
+        self.at(position.row as usize, Some(position.col as usize))
+    }
+
+    /// Create a syntax error of the given `format` from an XML parser error.
+    pub(crate) fn from_xml(format: &str, error: roxmltree::Error) -> ConversionError {
+        let position = error.pos();
+        let message = format!("Invalid {} document: {}", format.to_uppercase(), error);
+        ConversionError::new(format, ConversionErrorKind::Syntax, message)
+            .at(position.row as usize, Some(position.col as usize))
+    }
+
+    /// Create a syntax error of the given `format` from a JSON parser error.
+    pub(crate) fn from_json(format: &str, error: json::Error) -> ConversionError {
+        let message = format!("Invalid JSON: {}", error);
+        let result = ConversionError::new(format, ConversionErrorKind::Syntax, message);
+        match error {
+            json::Error::UnexpectedCharacter { line, column, .. } => result.at(line, Some(column)),
+            _ => result,
+        }
+    }
+
+    /// Create an error of the given `kind` from a `message` produced by `lib-param-bn`
+    /// while reading the `source` string of the given `format`.
+    ///
+    /// The offending identifier is the first quoted name in the message. The location is
+    /// the XML position in the message (`TextPos { row: 3, col: 14 }`), if there is one.
+    /// Otherwise, it is the first occurrence of the quoted text in the `source`.
+    pub fn from_message(
+        format: &str,
+        kind: ConversionErrorKind,
+        message: String,
+        source: &str,
+    ) -> ConversionError {
+        let quoted_re = Regex::new(r#"`([^`]+)`|'([^']+)'|"([^"]+)""#).unwrap();
+        let name_re = Regex::new(r"^[a-zA-Z0-9_]+$").unwrap();
+        let position_re = Regex::new(r"row: (\d+), col: (\d+)").unwrap();
+
+        let quoted = quoted_re.captures(&message).and_then(|c| {
+            c.iter()
+                .skip(1)
+                .flatten()
+                .next()
+                .map(|m| m.as_str().to_string())
+        });
+        let position = position_re
+            .captures(&message)
+            .map(|c| (c[1].parse().unwrap(), c[2].parse().unwrap()))
+            .or_else(|| {
+                quoted
+                    .as_ref()
+                    .and_then(|text| find_in_source(source, text))
+            });
+
+        let mut error = ConversionError::new(format, kind, message);
+        if let Some((line, column)) = position {
+            error = error.at(line, Some(column));
+        }
+        if let Some(quoted) = quoted.filter(|text| name_re.is_match(text)) {
+            error = error.with_identifier(&quoted);
+        }
+        error
+    }
+
+    /// A function which converts error messages about the `source` string of the given
+    /// `format` into a `ConversionError` of the given `kind` using `from_message`.
+    pub(crate) fn in_format<'a>(
+        format: &'a str,
+        kind: ConversionErrorKind,
+        source: &'a str,
+    ) -> impl Fn(String) -> ConversionError + 'a {
+        move |message| ConversionError::from_message(format, kind, message, source)
+    }
+}
+
+/// **(internal)** Find the first occurrence of `text` in the `source` string and return
+/// its line and column (both starting at 1). Names only match whole words.
+fn find_in_source(source: &str, text: &str) -> Option<(usize, usize)> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let pattern = regex::escape(text);
+    let is_name = text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let re = if is_name {
+        Regex::new(&format!(r"\b{}\b", pattern))
+    } else {
+        Regex::new(&pattern)
+    };
+    let re = re.ok()?;
+    source.lines().enumerate().find_map(|(i, line)| {
+        re.find(line)
+            .map(|m| (i + 1, line[..m.start()].chars().count() + 1))
+    })
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl From<ConversionError> for String {
+    fn from(value: ConversionError) -> Self {
+        value.message
+    }
+}
+
+/// **(internal)** Check that every layout comment (a line starting with `prefix`, followed
+/// by `var:x,y`) in the `source` string has valid coordinates. Comments of variables which
+/// are not in the network are ignored, the same as in `read_layout`.
+pub(crate) fn check_layout(
+    format: &str,
+    source: &str,
+    prefix: &str,
+) -> Result<(), ConversionError> {
+    for (i, line) in source.lines().enumerate() {
+        let column = line.len() - line.trim_start().len() + 1;
+        let position = match line.trim().strip_prefix(prefix) {
+            Some(position) => position,
+            None => continue,
+        };
+        let is_valid = position
+            .split_once(':')
+            .and_then(|(_, coordinates)| coordinates.split_once(','))
+            .is_some_and(|(x, y)| {
+                x.trim().parse::<f64>().is_ok() && y.trim().parse::<f64>().is_ok()
+            });
+        if !is_valid {
+            let message = format!("Line {}: invalid position `{}`.", i + 1, position);
+            return Err(
+                ConversionError::new(format, ConversionErrorKind::InvalidLayout, message)
+                    .at(i + 1, Some(column)),
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversions::ConversionError;
+    use crate::conversions::ConversionErrorKind::{InvalidLayout, Syntax, UnknownVariable};
+    use crate::Conversions;
+
+    #[test]
+    fn conversion_errors_from_messages() {
+        let source = "a -> b\n$b: ab\n";
+        let message = "Invalid update function. Unknown variable `ab`.".to_string();
+        let error = ConversionError::from_message("aeon", UnknownVariable, message, source);
+        assert_eq!(UnknownVariable, error.kind);
+        assert_eq!(Some("ab".to_string()), error.identifier);
+        assert_eq!((Some(2), Some(5)), (error.line, error.column));
+
+        let message = "XML Error: UnexpectedCloseTag(\"a\", \"b\", TextPos { row: 3, col: 5 })";
+        let error = ConversionError::from_message("sbml", Syntax, message.to_string(), "");
+        assert_eq!(Syntax, error.kind);
+        assert_eq!((Some(3), Some(5)), (error.line, error.column));
+
+        let error = Conversions::bnet_to_aeon("targets, factors\na, a\n  b = a\n").unwrap_err();
+        assert_eq!((Some(3), Some(3)), (error.line, error.column));
+        assert_eq!(None, error.identifier);
+
+        let error = Conversions::aeon_to_bnet("a -> b\nb -x a\n").unwrap_err();
+        assert_eq!(Syntax, error.kind);
+        assert_eq!((Some(2), Some(1)), (error.line, error.column));
+    }
+
+    #[test]
+    fn conversion_error_locations() {
+        let error = Conversions::sbml_to_aeon("<sbml><model><broken></model></sbml>").unwrap_err();
+        assert_eq!(Syntax, error.kind);
+        assert_eq!((Some(1), Some(22)), (error.line, error.column));
+
+        // Positions of variables which are not in the network are ignored.
+        let aeon = "a -> b\nb -| a\n$a: !b\n$b: a\n#position:iso:5,6\n";
+        assert!(Conversions::aeon_to_bnet(aeon).is_ok());
+        let aeon = "a -> b\nb -| a\n$a: !b\n$b: a\n  #position:a:5;6\n";
+        let error = Conversions::aeon_to_bnet(aeon).unwrap_err();
+        assert_eq!(InvalidLayout, error.kind);
+        assert_eq!((Some(5), Some(3)), (error.line, error.column));
+    }
+}
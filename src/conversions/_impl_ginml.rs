@@ -0,0 +1,279 @@
+use crate::conversions::ConversionError;
+use crate::conversions::ConversionErrorKind::{Syntax, UnknownVariable, UnsupportedFeature};
+use crate::static_constraints::tighten_regulations;
+use crate::Conversions;
+use biodivine_lib_param_bn::{BooleanNetwork, Monotonicity, RegulatoryGraph};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// **(internal)** The contents of a GINML file relevant for a Boolean network.
+#[derive(Debug, PartialEq)]
+pub(crate) struct GinmlModel {
+    variables: Vec<String>,
+    // (source, target, monotonicity)
+    regulations: Vec<(String, String, Option<Monotonicity>)>,
+    functions: Vec<(String, String)>,
+    positions: Vec<(String, (f64, f64))>,
+}
+
+#[wasm_bindgen]
+impl Conversions {
+    /// Convert a GINsim `.ginml` model into a model encoded as an `.aeon` string.
+    ///
+    /// Update functions are reconstructed from the logical parameters (or logical function
+    /// expressions) of every node, regulation monotonicity is taken from the edge signs,
+    /// and node positions are preserved as `#position:` metadata. Only Boolean nodes
+    /// (`maxvalue="1"`) are supported.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    pub fn ginml_to_aeon(ginml_string: &str) -> Result<String, ConversionError> {
+        let error = ConversionError::in_format("ginml", Syntax, ginml_string);
+        let model = parse_ginml(ginml_string)?;
+        let mut graph = RegulatoryGraph::new(model.variables.clone());
+        for (source, target, monotonicity) in &model.regulations {
+            graph
+                .add_regulation(source, target, false, *monotonicity)
+                .map_err(&error)?;
+        }
+        let mut network = BooleanNetwork::new(graph);
+        for (variable, function) in &model.functions {
+            network
+                .add_string_update_function(variable, function)
+                .map_err(&error)?;
+        }
+        let network = tighten_regulations(&network).map_err(&error)?;
+
+        let mut model_string = format!("{}\n", network);
+        for (variable, (x, y)) in &model.positions {
+            model_string += format!("#position:{}:{},{}\n", variable, x, y).as_str();
+        }
+        Ok(model_string)
+    }
+}
+
+/// **(internal)** Read the nodes, edges, update functions and layout of a GINML file.
+pub(crate) fn parse_ginml(ginml_string: &str) -> Result<GinmlModel, ConversionError> {
+    let error = |kind, message: String| ConversionError::new("ginml", kind, message);
+    let document = roxmltree::Document::parse(ginml_string)
+        .map_err(|e| ConversionError::from_xml("ginml", e))?;
+    let graph = document
+        .descendants()
+        .find(|n| n.has_tag_name("graph"))
+        .ok_or_else(|| {
+            error(
+                Syntax,
+                "Missing `graph` element in the GINML document.".to_string(),
+            )
+        })?;
+    let nodes = graph
+        .children()
+        .filter(|n| n.has_tag_name("node"))
+        .collect::<Vec<_>>();
+
+    let mut model = GinmlModel {
+        variables: Vec::new(),
+        regulations: Vec::new(),
+        functions: Vec::new(),
+        positions: Vec::new(),
+    };
+    for node in &nodes {
+        let id = node.attribute("id").ok_or_else(|| {
+            error(Syntax, "GINML node is missing an `id`.".to_string()).at_node(*node)
+        })?;
+        let max_value = node.attribute("maxvalue").unwrap_or("1");
+        if max_value != "1" {
+            let message = format!(
+                "Node `{}` is multi-valued (maxvalue {}), which is not supported.",
+                id, max_value
+            );
+            return Err(error(UnsupportedFeature, message)
+                .at_node(*node)
+                .with_identifier(id));
+        }
+        model.variables.push(id.to_string());
+        // Coordinates are either attributes of `nodevisualsetting`, or of its shape element.
+        let position = node
+            .children()
+            .filter(|n| n.has_tag_name("nodevisualsetting"))
+            .flat_map(|n| n.descendants())
+            .find_map(|n| {
+                Some((
+                    n.attribute("x")?.parse().ok()?,
+                    n.attribute("y")?.parse().ok()?,
+                ))
+            });
+        if let Some(position) = position {
+            model.positions.push((id.to_string(), position));
+        }
+    }
+
+    // Edges are identified by their id, which is used by logical parameters.
+    let mut edges: HashMap<&str, (&str, &str)> = HashMap::new();
+    let mut regulators: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in graph.children().filter(|n| n.has_tag_name("edge")) {
+        let (source, target) = match (edge.attribute("from"), edge.attribute("to")) {
+            (Some(source), Some(target)) => (source, target),
+            _ => {
+                let message = "GINML edge is missing `from` or `to`.".to_string();
+                return Err(error(Syntax, message).at_node(edge));
+            }
+        };
+        if edge.attribute("minvalue").unwrap_or("1") != "1" {
+            let message = format!(
+                "Edge `{}` -> `{}` has a threshold other than 1, which is not supported.",
+                source, target
+            );
+            return Err(error(UnsupportedFeature, message).at_node(edge));
+        }
+        let monotonicity = match edge.attribute("sign") {
+            Some("positive") => Some(Monotonicity::Activation),
+            Some("negative") => Some(Monotonicity::Inhibition),
+            _ => None,
+        };
+        let id = edge.attribute("id").unwrap_or("");
+        edges.insert(id, (source, target));
+        let target_regulators = regulators.entry(target).or_default();
+        if !target_regulators.contains(&source) {
+            target_regulators.push(source);
+            model
+                .regulations
+                .push((source.to_string(), target.to_string(), monotonicity));
+        }
+    }
+
+    for node in &nodes {
+        let id = node.attribute("id").unwrap();
+        let base_value = node.attribute("basevalue").unwrap_or("0") == "1";
+        // Newer GINsim versions can store logical functions directly.
+        let expressions = node
+            .children()
+            .filter(|n| n.has_tag_name("value") && n.attribute("val") == Some("1"))
+            .flat_map(|value| value.children().filter(|n| n.has_tag_name("exp")))
+            .filter_map(|exp| exp.attribute("str"))
+            .map(|exp| format!("({})", exp))
+            .collect::<Vec<_>>();
+        let function = if !expressions.is_empty() {
+            expressions.join(" | ")
+        } else {
+            let node_regulators = regulators.get(id).cloned().unwrap_or_default();
+            let mut terms = Vec::new();
+            for parameter in node.children().filter(|n| n.has_tag_name("parameter")) {
+                let value = parameter.attribute("val").unwrap_or("0");
+                if value != "0" && value != "1" {
+                    let message = format!(
+                        "Node `{}` has a logical parameter with value {}.",
+                        id, value
+                    );
+                    return Err(error(UnsupportedFeature, message)
+                        .at_node(parameter)
+                        .with_identifier(id));
+                }
+                let mut active = Vec::new();
+                for edge in parameter
+                    .attribute("idActiveInteractions")
+                    .unwrap_or("")
+                    .split_whitespace()
+                {
+                    match edges.get(edge) {
+                        Some((source, _)) => active.push(*source),
+                        None => {
+                            let message =
+                                format!("Unknown interaction `{}` of node `{}`.", edge, id);
+                            return Err(error(UnknownVariable, message)
+                                .at_node(parameter)
+                                .with_identifier(edge));
+                        }
+                    }
+                }
+                let term = node_regulators
+                    .iter()
+                    .map(|r| {
+                        if active.contains(r) {
+                            r.to_string()
+                        } else {
+                            format!("!{}", r)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                let term = if term.is_empty() {
+                    "true".to_string()
+                } else {
+                    format!("({})", term.join(" & "))
+                };
+                terms.push((value == "1", term));
+            }
+            // Parameters with value 1 make the function true, the remaining regulator
+            // configurations (not covered by any parameter) use the base value.
+            let mut clauses = terms
+                .iter()
+                .filter(|(value, _)| *value)
+                .map(|(_, term)| term.clone())
+                .collect::<Vec<_>>();
+            if base_value {
+                let covered = terms
+                    .iter()
+                    .map(|(_, term)| term.clone())
+                    .collect::<Vec<_>>();
+                if covered.is_empty() {
+                    clauses.push("true".to_string());
+                } else {
+                    clauses.push(format!("!({})", covered.join(" | ")));
+                }
+            }
+            if clauses.is_empty() {
+                "false".to_string()
+            } else {
+                clauses.join(" | ")
+            }
+        };
+        model.functions.push((id.to_string(), function));
+    }
+
+    Ok(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversions::_impl_ginml::parse_ginml;
+
+    #[test]
+    fn ginml_logical_parameters() {
+        let ginml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <gxl xmlns:xlink="http://www.w3.org/1999/xlink">
+            <graph class="regulatory" id="test" nodeorder="A B">
+              <node id="A" maxvalue="1">
+                <parameter idActiveInteractions="B:A" val="1"/>
+                <nodevisualsetting x="10" y="20"/>
+              </node>
+              <node id="B" maxvalue="1" basevalue="1">
+                <parameter idActiveInteractions="A:B" val="0"/>
+                <nodevisualsetting><ellipse x="30" y="40"/></nodevisualsetting>
+              </node>
+              <edge id="B:A" from="B" to="A" minvalue="1" sign="positive"/>
+              <edge id="A:B" from="A" to="B" minvalue="1" sign="negative"/>
+            </graph>
+            </gxl>"#;
+        let model = parse_ginml(ginml).unwrap();
+        assert_eq!(vec!["A", "B"], model.variables);
+        assert_eq!(
+            vec![
+                ("A".to_string(), "(B)".to_string()),
+                ("B".to_string(), "!((A))".to_string())
+            ],
+            model.functions
+        );
+        assert_eq!(
+            vec![
+                ("A".to_string(), (10.0, 20.0)),
+                ("B".to_string(), (30.0, 40.0))
+            ],
+            model.positions
+        );
+
+        let multivalued = ginml.replace("maxvalue=\"1\" basevalue", "maxvalue=\"2\" basevalue");
+        let error = parse_ginml(&multivalued).unwrap_err();
+        assert!(error.message.contains("Node `B`"));
+        assert_eq!(Some("B".to_string()), error.identifier);
+        assert_eq!(Some(8), error.line);
+    }
+}
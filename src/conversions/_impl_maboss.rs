@@ -0,0 +1,165 @@
+use crate::conversions::{instantiate_network, unique_names, ConversionError, ConversionErrorKind};
+use crate::Conversions;
+use biodivine_lib_param_bn::{BinaryOp, FnUpdate};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+/// **(internal)** The pair of files describing a MaBoSS model.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct MabossFiles {
+    bnd: String,
+    cfg: String,
+}
+
+#[wasm_bindgen]
+impl Conversions {
+    /// Convert a Boolean network model encoded as an `.aeon` string into a MaBoSS model.
+    ///
+    /// The result is an object with a `bnd` string (node logic with default up/down rates
+    /// `$u_x` and `$d_x` set to 1) and a `cfg` string (rates, a uniformly random initial
+    /// state and default simulation settings). The optional `outputs` is a whitespace
+    /// separated list of variables which are reported by MaBoSS; the remaining variables are
+    /// marked as internal. By default, all variables are outputs.
+    ///
+    /// Networks with uninterpreted parameters or implicit update functions are
+    /// instantiated using a witness network.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    pub fn aeon_to_maboss(
+        aeon_string: &str,
+        outputs: Option<String>,
+    ) -> Result<JsValue, ConversionError> {
+        let error = ConversionError::in_format("aeon", ConversionErrorKind::Syntax, aeon_string);
+        let network = instantiate_network(aeon_string).map_err(error)?;
+        let variables = network
+            .variables()
+            .map(|v| network.get_variable_name(v).clone())
+            .collect::<Vec<_>>();
+        let names = unique_names(&variables, &[], maboss_name);
+        let functions = network
+            .variables()
+            .map(|v| {
+                let function = network.get_update_function(v).as_ref().unwrap();
+                maboss_function(&names, function)
+            })
+            .collect::<Vec<_>>();
+        let outputs = match outputs {
+            Some(outputs) => {
+                let mut result = Vec::new();
+                for output in outputs.split_whitespace() {
+                    match variables.iter().position(|name| name == output) {
+                        Some(index) => result.push(names[index].clone()),
+                        None => {
+                            let message = format!("Unknown output variable `{}`.", output);
+                            return Err(ConversionError::new(
+                                "aeon",
+                                ConversionErrorKind::UnknownVariable,
+                                message,
+                            )
+                            .with_identifier(output));
+                        }
+                    }
+                }
+                result
+            }
+            None => names.clone(),
+        };
+        let files = write_maboss(&names, &functions, &outputs);
+        Ok(serde_wasm_bindgen::to_value(&files).unwrap())
+    }
+}
+
+/// **(internal)** Write the `.bnd` and `.cfg` files for the given variables and their
+/// (already translated) update functions.
+pub(crate) fn write_maboss(
+    names: &[String],
+    functions: &[String],
+    outputs: &[String],
+) -> MabossFiles {
+    let mut bnd = String::new();
+    for (name, function) in names.iter().zip(functions) {
+        bnd += format!(
+            "Node {} {{\n  logic = {};\n  rate_up = @logic ? $u_{} : 0;\n  rate_down = @logic ? 0 : $d_{};\n}}\n\n",
+            name, function, name, name
+        )
+        .as_str();
+    }
+
+    let mut cfg = String::new();
+    for name in names {
+        cfg += format!("$u_{} = 1;\n$d_{} = 1;\n", name, name).as_str();
+    }
+    cfg += "\n";
+    for name in names {
+        cfg += format!("[{}].istate = 0.5 [0], 0.5 [1];\n", name).as_str();
+    }
+    cfg += "\n";
+    for name in names {
+        let is_internal = !outputs.contains(name);
+        cfg += format!("{}.is_internal = {};\n", name, is_internal as u8).as_str();
+    }
+    cfg += "\ntime_tick = 0.5;\nmax_time = 100;\nsample_count = 10000;\ndiscrete_time = 0;\n";
+    cfg += "use_physrandgen = 1;\nthread_count = 1;\n";
+
+    MabossFiles { bnd, cfg }
+}
+
+/// **(internal)** Make the given variable name a valid MaBoSS node name. The result can
+/// clash with other names, so it should be used through `unique_names`.
+fn maboss_name(name: &str) -> String {
+    let name = name.replace(['{', '}'], "_");
+    if name.chars().next().map(|c| c.is_ascii_digit()) == Some(true) {
+        format!("v_{}", name)
+    } else {
+        name
+    }
+}
+
+/// **(internal)** Write a parameter-free update function using the MaBoSS operators.
+fn maboss_function(names: &[String], function: &FnUpdate) -> String {
+    match function {
+        FnUpdate::Const(value) => if *value { "1" } else { "0" }.to_string(),
+        FnUpdate::Var(var) => names[var.to_index()].clone(),
+        FnUpdate::Param(_, _) => unreachable!("The network is instantiated."),
+        FnUpdate::Not(inner) => format!("!{}", maboss_function(names, inner)),
+        FnUpdate::Binary(op, left, right) => {
+            let (l, r) = (maboss_function(names, left), maboss_function(names, right));
+            match op {
+                BinaryOp::And => format!("({} & {})", l, r),
+                BinaryOp::Or => format!("({} | {})", l, r),
+                BinaryOp::Xor => format!("({} ^ {})", l, r),
+                BinaryOp::Imp => format!("(!{} | {})", l, r),
+                BinaryOp::Iff => format!("!({} ^ {})", l, r),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversions::_impl_maboss::{maboss_name, write_maboss};
+    use crate::conversions::unique_names;
+
+    #[test]
+    fn maboss_bnd_and_cfg() {
+        let names = vec!["a".to_string(), "b".to_string()];
+        let functions = vec!["!b".to_string(), "a".to_string()];
+        let files = write_maboss(&names, &functions, &["b".to_string()]);
+        assert!(files.bnd.contains(
+            "Node a {\n  logic = !b;\n  rate_up = @logic ? $u_a : 0;\n  rate_down = @logic ? 0 : $d_a;\n}\n"
+        ));
+        assert!(files.cfg.contains("$u_b = 1;\n$d_b = 1;\n"));
+        assert!(files.cfg.contains("[a].istate = 0.5 [0], 0.5 [1];\n"));
+        assert!(files
+            .cfg
+            .contains("a.is_internal = 1;\nb.is_internal = 0;\n"));
+    }
+
+    #[test]
+    fn maboss_names_are_unique() {
+        let names = ["1x", "v_1x", "a{b}", "a_b_"].map(String::from);
+        let expected = ["v_1x_2", "v_1x", "a_b__2", "a_b_"].map(String::from);
+        assert_eq!(expected.to_vec(), unique_names(&names, &[], maboss_name));
+    }
+}
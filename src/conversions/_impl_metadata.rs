@@ -0,0 +1,337 @@
+use crate::conversions::{AeonMetadata, Annotations};
+use regex::{Captures, Regex};
+use std::collections::BTreeMap;
+
+/// Try to read the model metadata (`#name:`, `#description:`, and `#note:`, `#reference:`
+/// or `#tag:` annotations) from the given aeon file.
+///
+/// Annotations of a variable are written as `#note:var:text`, annotations of a regulation
+/// as `#note:source->target:text`. Multiple `#description:` lines are joined using
+/// a newline.
+pub fn read_metadata(aeon_string: &str) -> AeonMetadata {
+    let info_re = Regex::new(r"^\s*#(?P<kind>name|description):(?P<value>.*?)\s*$").unwrap();
+    let annotation_re = Regex::new(
+        r"^\s*#(?P<kind>note|reference|tag):(?P<source>[a-zA-Z0-9_{}]+)(->(?P<target>[a-zA-Z0-9_{}]+))?:(?P<value>.*?)\s*$",
+    )
+    .unwrap();
+    let mut metadata = AeonMetadata::default();
+    let mut description = Vec::new();
+    for line in aeon_string.lines() {
+        if let Some(captures) = info_re.captures(line) {
+            let value = captures["value"].to_string();
+            if &captures["kind"] == "name" {
+                metadata.name = value;
+            } else {
+                description.push(value);
+            }
+        } else if let Some(captures) = annotation_re.captures(line) {
+            let source = captures["source"].to_string();
+            let annotations = match captures.name("target") {
+                Some(target) => metadata
+                    .regulations
+                    .entry((source, target.as_str().to_string()))
+                    .or_default(),
+                None => metadata.variables.entry(source).or_default(),
+            };
+            let value = captures["value"].to_string();
+            match &captures["kind"] {
+                "note" => annotations.notes.push(value),
+                "reference" => annotations.references.push(value),
+                _ => annotations.tags.push(value),
+            }
+        }
+    }
+    metadata.description = description.join("\n");
+    metadata
+}
+
+/// Write the model metadata as aeon comments, such that they can be read again
+/// using `read_metadata`.
+pub fn write_metadata(metadata: &AeonMetadata) -> String {
+    let mut result = String::new();
+    if !metadata.name.is_empty() {
+        result += format!("#name:{}\n", single_line(&metadata.name)).as_str();
+    }
+    if !metadata.description.is_empty() {
+        for line in metadata.description.lines() {
+            result += format!("#description:{}\n", line).as_str();
+        }
+    }
+    for (var, annotations) in &metadata.variables {
+        result += write_annotations(var, annotations).as_str();
+    }
+    for ((source, target), annotations) in &metadata.regulations {
+        result += write_annotations(&format!("{}->{}", source, target), annotations).as_str();
+    }
+    result
+}
+
+/// **(internal)** Write all annotations of one variable or regulation.
+fn write_annotations(key: &str, annotations: &Annotations) -> String {
+    let mut result = String::new();
+    let kinds = [
+        ("note", &annotations.notes),
+        ("reference", &annotations.references),
+        ("tag", &annotations.tags),
+    ];
+    for (kind, values) in kinds {
+        for value in values {
+            result += format!("#{}:{}:{}\n", kind, key, single_line(value)).as_str();
+        }
+    }
+    result
+}
+
+/// **(internal)** Metadata are stored in line comments, so they cannot contain newlines.
+fn single_line(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+
+/// Add the model name, description and annotations to an SBML document as `name`
+/// attribute and XHTML `notes` elements. Variable annotations are added to the
+/// qualitative species, regulation annotations to the matching transition inputs.
+pub(crate) fn add_sbml_notes(sbml_string: &str, metadata: &AeonMetadata) -> String {
+    let model_re = Regex::new(r"<model(?P<attributes>[^>]*?)(?P<end>/?)>").unwrap();
+    let sbml_string = model_re.replacen(sbml_string, 1, |captures: &Captures| {
+        let mut attributes = captures["attributes"].to_string();
+        if !metadata.name.is_empty() && !attributes.contains(" name=") {
+            attributes += format!(" name=\"{}\"", escape_xml(&metadata.name)).as_str();
+        }
+        let notes = if metadata.description.is_empty() {
+            String::new()
+        } else {
+            let paragraph = format!(
+                "<p class=\"description\">{}</p>",
+                escape_xml(&metadata.description)
+            );
+            xhtml_notes(&paragraph)
+        };
+        if &captures["end"] == "/" {
+            format!("<model{}>{}</model>", attributes, notes)
+        } else {
+            format!("<model{}>{}", attributes, notes)
+        }
+    });
+
+    let species_re = Regex::new(
+        r#"(?P<tag><qual:qualitativeSpecies[^>]*?qual:id="(?P<id>[^"]+)"[^>]*?)(?P<end>/?)>"#,
+    )
+    .unwrap();
+    let sbml_string = species_re.replace_all(&sbml_string, |captures: &Captures| {
+        let annotations = metadata.variables.get(&captures["id"]);
+        with_notes(captures, "qual:qualitativeSpecies", annotations)
+    });
+
+    let transition_re = Regex::new(r"(?s)<qual:transition\b.*?</qual:transition>").unwrap();
+    let output_re =
+        Regex::new(r#"<qual:output\b[^>]*?qual:qualitativeSpecies="(?P<id>[^"]+)""#).unwrap();
+    let input_re = Regex::new(
+        r#"(?P<tag><qual:input\b[^>]*?qual:qualitativeSpecies="(?P<id>[^"]+)"[^>]*?)(?P<end>/?)>"#,
+    )
+    .unwrap();
+    let sbml_string = transition_re.replace_all(&sbml_string, |transition: &Captures| {
+        let target = match output_re.captures(&transition[0]) {
+            Some(output) => output["id"].to_string(),
+            None => return transition[0].to_string(),
+        };
+        input_re
+            .replace_all(&transition[0], |captures: &Captures| {
+                let key = (captures["id"].to_string(), target.clone());
+                with_notes(captures, "qual:input", metadata.regulations.get(&key))
+            })
+            .to_string()
+    });
+    sbml_string.to_string()
+}
+
+/// **(internal)** Add the given `annotations` as `notes` to an XML element which was
+/// matched as `tag` (the start tag without its end) and `end` (`/` for an empty element).
+fn with_notes(captures: &Captures, element: &str, annotations: Option<&Annotations>) -> String {
+    let notes = match annotations {
+        Some(annotations) => {
+            let mut paragraphs = String::new();
+            let kinds = [
+                ("note", &annotations.notes),
+                ("reference", &annotations.references),
+                ("tag", &annotations.tags),
+            ];
+            for (kind, values) in kinds {
+                for value in values {
+                    paragraphs +=
+                        format!("<p class=\"{}\">{}</p>", kind, escape_xml(value)).as_str();
+                }
+            }
+            xhtml_notes(&paragraphs)
+        }
+        None => String::new(),
+    };
+    let tag = &captures["tag"];
+    if notes.is_empty() {
+        captures[0].to_string()
+    } else if &captures["end"] == "/" {
+        format!("{}>{}</{}>", tag, notes, element)
+    } else {
+        format!("{}>{}", tag, notes)
+    }
+}
+
+/// Read the model name, description and annotations written by `add_sbml_notes` from
+/// an SBML document. Notes paragraphs without a recognized `class` are read as description
+/// (for the model) or as notes (for variables and regulations).
+pub(crate) fn read_sbml_notes(sbml_string: &str) -> AeonMetadata {
+    let mut metadata = AeonMetadata::default();
+    let document = match roxmltree::Document::parse(sbml_string) {
+        Ok(document) => document,
+        Err(_) => return metadata,
+    };
+    let paragraphs = |node: roxmltree::Node| {
+        node.children()
+            .filter(|n| n.has_tag_name("notes"))
+            .flat_map(|notes| notes.descendants().filter(|n| n.tag_name().name() == "p"))
+            .map(|p| {
+                let text = p
+                    .descendants()
+                    .filter(|n| n.is_text())
+                    .filter_map(|n| n.text())
+                    .collect::<String>();
+                (
+                    p.attribute("class").unwrap_or("").to_string(),
+                    text.trim().to_string(),
+                )
+            })
+            .filter(|(_, text)| !text.is_empty())
+            .collect::<Vec<_>>()
+    };
+    // Attributes of SBML-qual elements are namespaced, so they are matched by name only.
+    let attribute = |node: roxmltree::Node, name: &str| {
+        node.attributes()
+            .find(|a| a.name() == name)
+            .map(|a| a.value().to_string())
+    };
+    if let Some(model) = document.descendants().find(|n| n.has_tag_name("model")) {
+        metadata.name = model.attribute("name").unwrap_or("").to_string();
+        let description = paragraphs(model)
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect::<Vec<_>>();
+        metadata.description = description.join("\n");
+    }
+    for species in document
+        .descendants()
+        .filter(|n| n.tag_name().name() == "qualitativeSpecies")
+    {
+        if let Some(id) = attribute(species, "id") {
+            read_annotations(&mut metadata.variables, id, paragraphs(species));
+        }
+    }
+    for transition in document
+        .descendants()
+        .filter(|n| n.tag_name().name() == "transition")
+    {
+        let target = transition
+            .descendants()
+            .find(|n| n.tag_name().name() == "output")
+            .and_then(|output| attribute(output, "qualitativeSpecies"));
+        let Some(target) = target else {
+            continue;
+        };
+        for input in transition
+            .descendants()
+            .filter(|n| n.tag_name().name() == "input")
+        {
+            if let Some(source) = attribute(input, "qualitativeSpecies") {
+                let key = (source, target.clone());
+                read_annotations(&mut metadata.regulations, key, paragraphs(input));
+            }
+        }
+    }
+    metadata
+}
+
+/// **(internal)** Add the annotations given as `(class, text)` notes `paragraphs` to the
+/// annotations of the given `key`.
+fn read_annotations<K: Ord>(
+    annotations: &mut BTreeMap<K, Annotations>,
+    key: K,
+    paragraphs: Vec<(String, String)>,
+) {
+    if paragraphs.is_empty() {
+        return;
+    }
+    let annotations = annotations.entry(key).or_default();
+    for (class, text) in paragraphs {
+        match class.as_str() {
+            "reference" => annotations.references.push(text),
+            "tag" => annotations.tags.push(text),
+            _ => annotations.notes.push(text),
+        }
+    }
+}
+
+/// **(internal)** Wrap the given XHTML content in an SBML `notes` element.
+fn xhtml_notes(content: &str) -> String {
+    if content.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "<notes><body xmlns=\"http://www.w3.org/1999/xhtml\">{}</body></notes>",
+            content
+        )
+    }
+}
+
+/// **(internal)** Escape the special XML characters in the given text.
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversions::_impl_metadata::{
+        add_sbml_notes, read_metadata, read_sbml_notes, write_metadata,
+    };
+    use crate::Conversions;
+
+    #[test]
+    fn metadata_round_trip() {
+        let aeon = "#name:Cell cycle\n#description:First line\n#description:Second line\n\
+            #note:a:Activated by <b> & c\n#reference:a:doi:10.1000/1\n#tag:a->b:curated\n";
+        let metadata = read_metadata(aeon);
+        assert_eq!("Cell cycle", metadata.name);
+        assert_eq!("First line\nSecond line", metadata.description);
+        assert_eq!(vec!["doi:10.1000/1"], metadata.variables["a"].references);
+        assert_eq!(aeon, write_metadata(&metadata));
+
+        let sbml = "<sbml xmlns:qual=\"http://www.sbml.org/sbml/level3/version1/qual/version1\">\
+            <model><qual:listOfQualitativeSpecies>\
+            <qual:qualitativeSpecies qual:id=\"a\"/>\
+            </qual:listOfQualitativeSpecies></model></sbml>";
+        let sbml = add_sbml_notes(sbml, &metadata);
+        let mut imported = read_sbml_notes(&sbml);
+        assert_eq!(metadata.name, imported.name);
+        assert_eq!(metadata.description, imported.description);
+        assert_eq!(metadata.variables, imported.variables);
+        // Without a transition, regulation annotations have nowhere to be stored.
+        imported.regulations = metadata.regulations.clone();
+        assert_eq!(metadata, imported);
+    }
+
+    #[test]
+    fn sbml_regulation_notes_round_trip() {
+        let aeon = "a -> b\nb -| a\n$a: !b\n$b: a\n\
+            #note:a:Variable note\n#note:a->b:First & <second>\n#tag:a->b:curated\n\
+            #reference:b->a:doi:10.1000/2\n";
+        let sbml = Conversions::aeon_to_sbml(aeon).unwrap();
+        let metadata = read_sbml_notes(&sbml);
+        assert_eq!(read_metadata(aeon), metadata);
+        let key = ("a".to_string(), "b".to_string());
+        assert_eq!(vec!["First & <second>"], metadata.regulations[&key].notes);
+
+        let imported = Conversions::sbml_to_aeon(&sbml).unwrap();
+        assert_eq!(read_metadata(aeon), read_metadata(&imported));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::conversions::_impl_primes::function_primes;
+use crate::conversions::ConversionErrorKind::Syntax;
+use crate::conversions::{instantiate_network, ConversionError};
+use crate::Conversions;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// **(internal)** A Petri net transition which changes the value of one variable.
+///
+/// The transition is enabled when the variable has the opposite value and all
+/// `conditions` (pairs of a variable index and its value) hold.
+pub(crate) struct PetriTransition {
+    variable: usize,
+    activation: bool,
+    conditions: Vec<(usize, bool)>,
+}
+
+#[wasm_bindgen]
+impl Conversions {
+    /// Convert a Boolean network model encoded as an `.aeon` string into a safe Petri net
+    /// in the `.pnml` format.
+    ///
+    /// Every variable `x` is represented by two places, `x_0` and `x_1`, exactly one of
+    /// which is marked. Every prime implicant of the update function (or its negation)
+    /// yields one transition which activates (or deactivates) `x`. Other variables in the
+    /// implicant are connected using read arcs (a pair of opposite arcs). The reachability
+    /// graph of the net thus corresponds to the asynchronous state-transition graph. In the
+    /// initial marking, all variables are inactive.
+    ///
+    /// Networks with uninterpreted parameters or implicit update functions are
+    /// instantiated using a witness network. Update functions with more than 20 inputs
+    /// are not supported.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    pub fn aeon_to_pnml(aeon_string: &str) -> Result<String, ConversionError> {
+        let error = ConversionError::in_format("aeon", Syntax, aeon_string);
+        let network = instantiate_network(aeon_string).map_err(error)?;
+        let names = network
+            .variables()
+            .map(|v| network.get_variable_name(v).clone())
+            .collect::<Vec<_>>();
+        let mut transitions = Vec::new();
+        for var in network.variables() {
+            let function = network.get_update_function(var).as_ref().unwrap();
+            let name = network.get_variable_name(var);
+            let (inputs, off, on) = function_primes(name, function)?;
+            for (activation, implicants) in [(true, on), (false, off)] {
+                for implicant in implicants {
+                    let mut conditions = Vec::new();
+                    let mut is_enabled = true;
+                    for (i, value) in implicant.into_iter().enumerate() {
+                        match value {
+                            // The value of the updated variable is given by the consumed place.
+                            Some(value) if inputs[i] == var => is_enabled = value != activation,
+                            Some(value) => conditions.push((inputs[i].to_index(), value)),
+                            None => (),
+                        }
+                    }
+                    if is_enabled {
+                        transitions.push(PetriTransition {
+                            variable: var.to_index(),
+                            activation,
+                            conditions,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(write_pnml(&names, &transitions))
+    }
+}
+
+/// **(internal)** Write the Petri net with the given variables and transitions as `.pnml`.
+pub(crate) fn write_pnml(names: &[String], transitions: &[PetriTransition]) -> String {
+    let place = |var: usize, value: bool| format!("p_{}_{}", names[var], value as u8);
+    let mut pnml = String::new();
+    pnml += "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n";
+    pnml += "<pnml xmlns=\"http://www.pnml.org/version-2009/grammar/pnml\">\n";
+    pnml += "  <net id=\"net\" type=\"http://www.pnml.org/version-2009/grammar/ptnet\">\n";
+    pnml += "    <page id=\"page\">\n";
+    for (var, name) in names.iter().enumerate() {
+        for value in [false, true] {
+            pnml += format!(
+                "      <place id=\"{}\"><name><text>{}_{}</text></name><initialMarking><text>{}</text></initialMarking></place>\n",
+                place(var, value),
+                name,
+                value as u8,
+                !value as u8,
+            )
+            .as_str();
+        }
+    }
+    let mut arcs = Vec::new();
+    for (i, transition) in transitions.iter().enumerate() {
+        let name = names[transition.variable].as_str();
+        let direction = if transition.activation { "up" } else { "down" };
+        let id = format!("t_{}_{}_{}", name, direction, i);
+        pnml += format!(
+            "      <transition id=\"{}\"><name><text>{}_{}</text></name></transition>\n",
+            id, name, direction
+        )
+        .as_str();
+        let from = place(transition.variable, !transition.activation);
+        let to = place(transition.variable, transition.activation);
+        arcs.push((from, id.clone()));
+        arcs.push((id.clone(), to));
+        for (var, value) in &transition.conditions {
+            arcs.push((place(*var, *value), id.clone()));
+            arcs.push((id.clone(), place(*var, *value)));
+        }
+    }
+    for (i, (source, target)) in arcs.into_iter().enumerate() {
+        pnml += format!(
+            "      <arc id=\"a_{}\" source=\"{}\" target=\"{}\"/>\n",
+            i, source, target
+        )
+        .as_str();
+    }
+    pnml += "    </page>\n";
+    pnml += "  </net>\n";
+    pnml += "</pnml>\n";
+    pnml
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversions::_impl_pnml::{write_pnml, PetriTransition};
+    use crate::Conversions;
+
+    #[test]
+    fn pnml_read_arcs() {
+        let names = vec!["a".to_string(), "b".to_string()];
+        let transitions = vec![PetriTransition {
+            variable: 0,
+            activation: true,
+            conditions: vec![(1, false)],
+        }];
+        let pnml = write_pnml(&names, &transitions);
+        assert!(pnml.contains("<place id=\"p_a_0\"><name><text>a_0</text></name><initialMarking><text>1</text></initialMarking></place>"));
+        assert!(pnml.contains("<transition id=\"t_a_up_0\">"));
+        assert!(pnml.contains("<arc id=\"a_0\" source=\"p_a_0\" target=\"t_a_up_0\"/>"));
+        assert!(pnml.contains("<arc id=\"a_1\" source=\"t_a_up_0\" target=\"p_a_1\"/>"));
+        assert!(pnml.contains("<arc id=\"a_2\" source=\"p_b_0\" target=\"t_a_up_0\"/>"));
+        assert!(pnml.contains("<arc id=\"a_3\" source=\"t_a_up_0\" target=\"p_b_0\"/>"));
+    }
+
+    #[test]
+    fn aeon_to_pnml_transitions() {
+        let aeon = "a -> b\nb -| a\nb -> b\n$a: !b\n$b: a | b\n";
+        let pnml = Conversions::aeon_to_pnml(aeon).unwrap();
+        // The implicants `b` (activation) and `!a & !b` (deactivation) of `b` are never
+        // enabled, because they require `b` to already have the target value.
+        assert_eq!(3, pnml.matches("<transition ").count());
+        assert!(pnml.contains("<transition id=\"t_a_up_0\">"));
+        assert!(pnml.contains("<transition id=\"t_a_down_1\">"));
+        assert!(pnml.contains("<transition id=\"t_b_up_2\">"));
+        let arcs = [
+            ("p_a_0", "t_a_up_0"),
+            ("t_a_up_0", "p_a_1"),
+            ("p_b_0", "t_a_up_0"),
+            ("t_a_up_0", "p_b_0"),
+            ("p_a_1", "t_a_down_1"),
+            ("t_a_down_1", "p_a_0"),
+            ("p_b_1", "t_a_down_1"),
+            ("t_a_down_1", "p_b_1"),
+            ("p_b_0", "t_b_up_2"),
+            ("t_b_up_2", "p_b_1"),
+            ("p_a_1", "t_b_up_2"),
+            ("t_b_up_2", "p_a_1"),
+        ];
+        assert_eq!(arcs.len(), pnml.matches("<arc ").count());
+        for (i, (source, target)) in arcs.iter().enumerate() {
+            let arc = format!(
+                "<arc id=\"a_{}\" source=\"{}\" target=\"{}\"/>",
+                i, source, target
+            );
+            assert!(pnml.contains(&arc), "Missing {}", arc);
+        }
+    }
+}
@@ -0,0 +1,347 @@
+use crate::conversions::ConversionErrorKind::{Syntax, UnknownVariable, UnsupportedFeature};
+use crate::conversions::{instantiate_network, ConversionError};
+use crate::static_constraints::tighten_regulations;
+use crate::Conversions;
+use biodivine_lib_bdd::{Bdd, BddVariableSet};
+use biodivine_lib_param_bn::{BinaryOp, BooleanNetwork, FnUpdate, RegulatoryGraph, VariableId};
+use json::JsonValue;
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// **(internal)** A conjunction of literals. The value at index `i` is `Some(value)` if
+/// the `i`-th input variable is fixed to `value`, and `None` if it is not restricted.
+pub(crate) type Implicant = Vec<Option<bool>>;
+
+/// **(internal)** The sorted inputs of a function, followed by the prime implicants of its
+/// negation ("off") and of the function itself ("on").
+pub(crate) type FunctionPrimes = (Vec<VariableId>, Vec<Implicant>, Vec<Implicant>);
+
+/// **(internal)** The maximal number of inputs of an update function whose prime implicants
+/// are computed. The computation is symbolic, but the number of prime implicants can still
+/// grow exponentially with the number of inputs.
+const MAX_PRIME_INPUTS: usize = 20;
+
+#[wasm_bindgen]
+impl Conversions {
+    /// Convert a Boolean network model encoded as an `.aeon` string into the PyBoolNet
+    /// "primes" JSON format.
+    ///
+    /// For every variable, the result contains a pair of lists with the prime implicants
+    /// of the negation and of the update function itself. Each implicant is an object which
+    /// maps variable names to `0` or `1`. Networks with uninterpreted parameters or implicit
+    /// update functions are instantiated using a witness network. Update functions with more
+    /// than 20 inputs are not supported.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    pub fn aeon_to_primes(aeon_string: &str) -> Result<String, ConversionError> {
+        let error = ConversionError::in_format("aeon", Syntax, aeon_string);
+        let network = instantiate_network(aeon_string).map_err(error)?;
+        let mut result = JsonValue::new_object();
+        for var in network.variables() {
+            let function = network.get_update_function(var).as_ref().unwrap();
+            let name = network.get_variable_name(var);
+            let (inputs, off, on) = function_primes(name, function)?;
+            let to_json = |implicants: Vec<Implicant>| {
+                let mut list = JsonValue::new_array();
+                for implicant in implicants {
+                    let mut prime = JsonValue::new_object();
+                    for (i, value) in implicant.into_iter().enumerate() {
+                        if let Some(value) = value {
+                            prime[network.get_variable_name(inputs[i]).as_str()] =
+                                (value as u8).into();
+                        }
+                    }
+                    list.push(prime).unwrap();
+                }
+                list
+            };
+            let (off, on) = (to_json(off), to_json(on));
+            result[network.get_variable_name(var).as_str()] = JsonValue::Array(vec![off, on]);
+        }
+        Ok(result.pretty(2))
+    }
+
+    /// Convert a Boolean network in the PyBoolNet "primes" JSON format into a model
+    /// encoded as an `.aeon` string.
+    ///
+    /// Update functions are rebuilt in disjunctive normal form from the prime implicants
+    /// of the "on" value. Regulations, including their monotonicity, are inferred from
+    /// the update functions.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    pub fn primes_to_aeon(primes_string: &str) -> Result<String, ConversionError> {
+        let error = ConversionError::in_format("primes", Syntax, primes_string);
+        let functions = parse_primes(primes_string)?;
+        let names = functions
+            .iter()
+            .map(|(name, _, _)| name.clone())
+            .collect::<Vec<_>>();
+        let mut graph = RegulatoryGraph::new(names);
+        for (target, _, regulators) in &functions {
+            for regulator in regulators {
+                graph
+                    .add_regulation(regulator, target, false, None)
+                    .map_err(&error)?;
+            }
+        }
+        let mut network = BooleanNetwork::new(graph);
+        for (target, function, _) in &functions {
+            network
+                .add_string_update_function(target, function)
+                .map_err(&error)?;
+        }
+        Ok(tighten_regulations(&network).map_err(&error)?.to_string())
+    }
+}
+
+/// **(internal)** Read the primes JSON and return the name, DNF update function and
+/// sorted regulators of every variable.
+pub(crate) fn parse_primes(
+    primes_string: &str,
+) -> Result<Vec<(String, String, Vec<String>)>, ConversionError> {
+    let error = |kind, message: String| ConversionError::new("primes", kind, message);
+    let json = json::parse(primes_string).map_err(|e| ConversionError::from_json("primes", e))?;
+    if !json.is_object() {
+        let message = "Expected a JSON object mapping variables to their primes.".to_string();
+        return Err(error(Syntax, message));
+    }
+    let names = json.entries().map(|(name, _)| name).collect::<HashSet<_>>();
+    let mut result = Vec::new();
+    for (name, primes) in json.entries() {
+        if primes.len() != 2 || !primes[0].is_array() || !primes[1].is_array() {
+            let message = format!("Expected a pair of prime lists for variable `{}`.", name);
+            return Err(error(Syntax, message).with_identifier(name));
+        }
+        let mut regulators = Vec::new();
+        let mut clauses = Vec::new();
+        for (index, implicants) in primes.members().enumerate() {
+            for implicant in implicants.members() {
+                let mut literals = Vec::new();
+                for (regulator, value) in implicant.entries() {
+                    if !names.contains(regulator) {
+                        let message = format!(
+                            "Unknown variable `{}` in the primes of `{}`.",
+                            regulator, name
+                        );
+                        return Err(error(UnknownVariable, message).with_identifier(regulator));
+                    }
+                    let literal = match value.as_u8() {
+                        Some(0) => format!("!{}", regulator),
+                        Some(1) => regulator.to_string(),
+                        _ => {
+                            let message = format!(
+                                "Invalid value of `{}` in the primes of `{}`.",
+                                regulator, name
+                            );
+                            return Err(error(Syntax, message).with_identifier(regulator));
+                        }
+                    };
+                    literals.push(literal);
+                    if !regulators.contains(&regulator.to_string()) {
+                        regulators.push(regulator.to_string());
+                    }
+                }
+                // Only the primes of the "on" value (index 1) define the function.
+                if index == 1 {
+                    clauses.push(if literals.is_empty() {
+                        "true".to_string()
+                    } else {
+                        format!("({})", literals.join(" & "))
+                    });
+                }
+            }
+        }
+        let function = if clauses.is_empty() {
+            "false".to_string()
+        } else {
+            clauses.join(" | ")
+        };
+        regulators.sort();
+        result.push((name.to_string(), function, regulators));
+    }
+    Ok(result)
+}
+
+/// **(internal)** Compute the prime implicants of the negation ("off") and of the function
+/// itself ("on") of a parameter-free update function of the variable `name`. Implicants
+/// are indexed using the returned sorted list of function inputs.
+///
+/// Functions with more than `MAX_PRIME_INPUTS` inputs are rejected.
+pub(crate) fn function_primes(
+    name: &str,
+    function: &FnUpdate,
+) -> Result<FunctionPrimes, ConversionError> {
+    let mut inputs = function.collect_arguments();
+    inputs.sort();
+    if inputs.len() > MAX_PRIME_INPUTS {
+        let message = format!(
+            "Update function of `{}` has {} inputs, but prime implicants are only supported \
+            for at most {} inputs.",
+            name,
+            inputs.len(),
+            MAX_PRIME_INPUTS
+        );
+        return Err(ConversionError::new("aeon", UnsupportedFeature, message).with_identifier(name));
+    }
+    let variables = BddVariableSet::new_anonymous(inputs.len() as u16);
+    let bdd = function_bdd(function, &variables, &inputs);
+    let off = prime_implicants(&bdd.not(), inputs.len());
+    let on = prime_implicants(&bdd, inputs.len());
+    Ok((inputs, off, on))
+}
+
+/// **(internal)** Build the `Bdd` of a parameter-free update function. The `i`-th `Bdd`
+/// variable corresponds to the `i`-th element of the sorted `inputs`.
+fn function_bdd(function: &FnUpdate, variables: &BddVariableSet, inputs: &[VariableId]) -> Bdd {
+    match function {
+        FnUpdate::Const(true) => variables.mk_true(),
+        FnUpdate::Const(false) => variables.mk_false(),
+        FnUpdate::Var(var) => {
+            let index = inputs.binary_search(var).unwrap();
+            variables.mk_var(variables.variables()[index])
+        }
+        FnUpdate::Param(_, _) => unreachable!("The network is instantiated."),
+        FnUpdate::Not(inner) => function_bdd(inner, variables, inputs).not(),
+        FnUpdate::Binary(op, left, right) => {
+            let left = function_bdd(left, variables, inputs);
+            let right = function_bdd(right, variables, inputs);
+            match op {
+                BinaryOp::And => left.and(&right),
+                BinaryOp::Or => left.or(&right),
+                BinaryOp::Xor => left.xor(&right),
+                BinaryOp::Imp => left.imp(&right),
+                BinaryOp::Iff => left.iff(&right),
+            }
+        }
+    }
+}
+
+/// **(internal)** Compute all prime implicants of a Boolean function with `num_inputs`
+/// inputs given as a `Bdd`. The result is sorted.
+pub(crate) fn prime_implicants(bdd: &Bdd, num_inputs: usize) -> Vec<Implicant> {
+    let mut primes = bdd_primes(bdd, num_inputs, &mut HashMap::new());
+    primes.sort();
+    primes
+}
+
+/// **(internal)** Recursively compute the prime implicants of `bdd` by splitting on its
+/// root variable `x`. The primes of `f` are the primes of `f[x=0] & f[x=1]`, together with
+/// the primes of `f[x=0]` (or `f[x=1]`) which are not primes of the conjunction, extended
+/// with the literal `!x` (or `x`). Results are cached for every visited function.
+fn bdd_primes(
+    bdd: &Bdd,
+    num_inputs: usize,
+    cache: &mut HashMap<Bdd, Vec<Implicant>>,
+) -> Vec<Implicant> {
+    if bdd.is_false() {
+        return Vec::new();
+    }
+    if bdd.is_true() {
+        return vec![vec![None; num_inputs]];
+    }
+    if let Some(primes) = cache.get(bdd) {
+        return primes.clone();
+    }
+    let var = bdd.var_of(bdd.root_pointer());
+    let low = bdd.var_restrict(var, false);
+    let high = bdd.var_restrict(var, true);
+    let both = bdd_primes(&low.and(&high), num_inputs, cache);
+    let shared = both.iter().cloned().collect::<HashSet<_>>();
+    let mut primes = both;
+    for (value, cofactor) in [(false, low), (true, high)] {
+        for mut implicant in bdd_primes(&cofactor, num_inputs, cache) {
+            if !shared.contains(&implicant) {
+                implicant[var.to_index()] = Some(value);
+                primes.push(implicant);
+            }
+        }
+    }
+    cache.insert(bdd.clone(), primes.clone());
+    primes
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversions::_impl_primes::{parse_primes, prime_implicants};
+    use crate::conversions::ConversionErrorKind::UnsupportedFeature;
+    use crate::Conversions;
+    use biodivine_lib_bdd::BddVariableSet;
+
+    #[test]
+    fn bdd_prime_implicants() {
+        // f(a, b, c) = (a & b) | !c
+        let variables = BddVariableSet::new(&["a", "b", "c"]);
+        let bdd = variables.eval_expression_string("(a & b) | !c");
+        assert_eq!(
+            vec![
+                vec![None, None, Some(false)],
+                vec![Some(true), Some(true), None],
+            ],
+            prime_implicants(&bdd, 3)
+        );
+        assert_eq!(
+            vec![
+                vec![None, Some(false), Some(true)],
+                vec![Some(false), None, Some(true)],
+            ],
+            prime_implicants(&bdd.not(), 3)
+        );
+        assert_eq!(
+            vec![vec![None, None, None]],
+            prime_implicants(&variables.mk_true(), 3)
+        );
+        assert!(prime_implicants(&variables.mk_false(), 3).is_empty());
+    }
+
+    #[test]
+    fn primes_near_input_limit() {
+        // f = (x0 & x1) | (x2 & x3) | ... | (x18 & x19) has 10 "on" primes and
+        // 2^10 "off" primes, each fixing one input of every pair to zero.
+        let inputs = (0..20).map(|i| format!("x{:02}", i)).collect::<Vec<_>>();
+        let clauses = inputs
+            .chunks(2)
+            .map(|pair| format!("({} & {})", pair[0], pair[1]))
+            .collect::<Vec<_>>();
+        let mut aeon = format!("$y: {}\n", clauses.join(" | "));
+        for input in &inputs {
+            aeon += format!("{} -> y\n", input).as_str();
+        }
+        let primes = json::parse(&Conversions::aeon_to_primes(&aeon).unwrap()).unwrap();
+        assert_eq!(1024, primes["y"][0].len());
+        assert_eq!(10, primes["y"][1].len());
+        assert!(primes["y"][0].members().all(|prime| prime.len() == 10));
+        assert!(primes["y"][1].members().all(|prime| prime.len() == 2));
+    }
+
+    #[test]
+    fn read_primes_json() {
+        let primes = r#"{ "A": [[{"B": 0}], [{"B": 1}]], "B": [[{"A": 1, "B": 1}], [{"A": 0}, {"B": 0}]], "C": [[], [{}]] }"#;
+        let functions = parse_primes(primes).unwrap();
+        assert_eq!(
+            ("A".to_string(), "(B)".to_string(), vec!["B".to_string()]),
+            functions[0]
+        );
+        assert_eq!("(!A) | (!B)", functions[1].1);
+        assert_eq!(vec!["A".to_string(), "B".to_string()], functions[1].2);
+        assert_eq!("true", functions[2].1);
+    }
+
+    #[test]
+    fn reject_invalid_networks() {
+        // The functions contradict the declared monotonicity of both regulations.
+        let aeon = "a -> b\nb -| a\n$a: b\n$b: !a\n";
+        assert!(Conversions::aeon_to_primes(aeon).is_err());
+        assert!(Conversions::aeon_to_sbml_instantiated(aeon).is_err());
+        assert!(Conversions::aeon_to_primes("a -> b\nb -| a\n$a: !b\n$b: a\n").is_ok());
+
+        // Functions with too many inputs are rejected instead of enumerated.
+        let inputs = (0..21).map(|i| format!("x{}", i)).collect::<Vec<_>>();
+        let mut aeon = format!("$y: {}\n", inputs.join(" & "));
+        for input in &inputs {
+            aeon += format!("{} -> y\n", input).as_str();
+        }
+        let error = Conversions::aeon_to_primes(&aeon).unwrap_err();
+        assert_eq!(UnsupportedFeature, error.kind);
+        assert_eq!(Some("y".to_string()), error.identifier);
+    }
+}
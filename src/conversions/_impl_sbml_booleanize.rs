@@ -0,0 +1,554 @@
+use crate::conversions::ConversionErrorKind::{Syntax, UnknownVariable, UnsupportedFeature};
+use crate::conversions::{ConversionError, ConversionErrorKind};
+use crate::static_constraints::tighten_regulations;
+use crate::Conversions;
+use biodivine_lib_param_bn::{BooleanNetwork, RegulatoryGraph};
+use regex::Regex;
+use roxmltree::Node;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+/// Describes how one (possibly multi-valued) SBML species is represented by Boolean
+/// variables. The level of the species is the number of `variables` which are true.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SpeciesMapping {
+    species: String,
+    max_level: u32,
+    variables: Vec<String>,
+}
+
+/// **(internal)** The result of the booleanization of an SBML-qual model.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct BooleanizedModel {
+    mapping: Vec<SpeciesMapping>,
+    functions: Vec<(String, String)>,
+    positions: Vec<(String, (f64, f64))>,
+}
+
+/// **(internal)** The result of `sbml_to_aeon_booleanized` sent to JavaScript.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct BooleanizedResult {
+    model: String,
+    mapping: Vec<SpeciesMapping>,
+}
+
+/// **(internal)** A MathML operand: either a species, or a constant level.
+enum Operand {
+    Species(String),
+    Level(i64),
+}
+
+#[wasm_bindgen]
+impl Conversions {
+    /// Convert an SBML-qual model with multi-valued species (`maxLevel > 1`) into
+    /// a Boolean model encoded as an `.aeon` string.
+    ///
+    /// A species `x` with `maxLevel = m > 1` is represented by threshold variables `x_b1`
+    /// to `x_bm`, where `x_bk` is true iff the level of `x` is at least `k`. If these names
+    /// are already taken, a numeric suffix is added to the species name (`x_2_b1`). The update
+    /// functions only allow unitary level changes and preserve the admissible states
+    /// (where `x_bk` implies `x_b(k-1)`). Boolean species keep their name. Regulation
+    /// monotonicity is inferred from the resulting update functions.
+    ///
+    /// The result is an object with the `model` string and a `mapping` list that describes
+    /// which variables represent each species, such that results can be projected back.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    pub fn sbml_to_aeon_booleanized(sbml_string: &str) -> Result<JsValue, ConversionError> {
+        let result = booleanized_aeon(sbml_string)?;
+        Ok(serde_wasm_bindgen::to_value(&result).unwrap())
+    }
+}
+
+/// **(internal)** Booleanize an SBML-qual model and build the resulting `.aeon` model
+/// (see `sbml_to_aeon_booleanized`).
+pub(crate) fn booleanized_aeon(sbml_string: &str) -> Result<BooleanizedResult, ConversionError> {
+    let error = ConversionError::in_format("sbml", Syntax, sbml_string);
+    let model = booleanize_sbml(sbml_string)?;
+    let names = model
+        .functions
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    let name_re = Regex::new(r"[A-Za-z0-9_]+").unwrap();
+    let mut graph = RegulatoryGraph::new(names);
+    for (target, function) in &model.functions {
+        let mut regulators = name_re
+            .find_iter(function)
+            .map(|m| m.as_str())
+            .filter(|name| *name != "true" && *name != "false")
+            .collect::<Vec<_>>();
+        regulators.sort();
+        regulators.dedup();
+        for regulator in regulators {
+            graph
+                .add_regulation(regulator, target, false, None)
+                .map_err(&error)?;
+        }
+    }
+    let mut network = BooleanNetwork::new(graph);
+    for (target, function) in &model.functions {
+        network
+            .add_string_update_function(target, function)
+            .map_err(&error)?;
+    }
+    let network = tighten_regulations(&network).map_err(&error)?;
+
+    let mut model_string = format!("{}\n", network);
+    for (variable, (x, y)) in &model.positions {
+        model_string += format!("#position:{}:{},{}\n", variable, x, y).as_str();
+    }
+    Ok(BooleanizedResult {
+        model: model_string,
+        mapping: model.mapping,
+    })
+}
+
+/// **(internal)** Read an SBML-qual model and translate it into Boolean update functions
+/// over threshold variables.
+pub(crate) fn booleanize_sbml(sbml_string: &str) -> Result<BooleanizedModel, ConversionError> {
+    let document = roxmltree::Document::parse(sbml_string)
+        .map_err(|e| ConversionError::from_xml("sbml", e))?;
+
+    let mut max_levels = HashMap::new();
+    let mut species_order = Vec::new();
+    for species in elements(document.root(), "qualitativeSpecies") {
+        let id = attribute(species, "id").ok_or_else(|| {
+            sbml_error(Syntax, "Species is missing an `id`.".to_string(), species)
+        })?;
+        let max_level = attribute(species, "maxLevel")
+            .unwrap_or_else(|| "1".to_string())
+            .parse::<u32>()
+            .map_err(|_| {
+                let message = format!("Invalid `maxLevel` of species `{}`.", id);
+                sbml_error(Syntax, message, species).with_identifier(&id)
+            })?;
+        max_levels.insert(id.clone(), max_level);
+        species_order.push(id);
+    }
+
+    // Threshold variables must not clash with species ids or other threshold variables.
+    let mut used = species_order.iter().cloned().collect::<HashSet<_>>();
+    let mut mapping = Vec::new();
+    for species in &species_order {
+        let max_level = max_levels[species];
+        let variables = if max_level <= 1 {
+            vec![species.clone()]
+        } else {
+            let thresholds = |prefix: &String| {
+                (1..=max_level)
+                    .map(|k| format!("{}_b{}", prefix, k))
+                    .collect::<Vec<_>>()
+            };
+            let variables = std::iter::once(species.clone())
+                .chain((2..).map(|i| format!("{}_{}", species, i)))
+                .map(|prefix| thresholds(&prefix))
+                .find(|variables| !variables.iter().any(|v| used.contains(v)))
+                .unwrap();
+            used.extend(variables.iter().cloned());
+            variables
+        };
+        mapping.push(SpeciesMapping {
+            species: species.clone(),
+            max_level,
+            variables,
+        });
+    }
+    // Builds the expression "level of `species` is at least `k`".
+    let mapping_of = mapping
+        .iter()
+        .map(|m| (m.species.as_str(), m))
+        .collect::<HashMap<_, _>>();
+    let at_least = |species: &str, k: i64| -> Result<String, ConversionError> {
+        let species_mapping = mapping_of.get(species).ok_or_else(|| {
+            let message = format!("Unknown species `{}`.", species);
+            ConversionError::new("sbml", UnknownVariable, message).with_identifier(species)
+        })?;
+        Ok(if k <= 0 {
+            "true".to_string()
+        } else if k > species_mapping.max_level as i64 {
+            "false".to_string()
+        } else {
+            species_mapping.variables[(k - 1) as usize].clone()
+        })
+    };
+
+    // Target level expressions of every species with a transition.
+    let mut targets: HashMap<String, (u32, Vec<(u32, String)>)> = HashMap::new();
+    for transition in elements(document.root(), "transition") {
+        let mut thresholds = HashMap::new();
+        for input in elements(transition, "input") {
+            if let (Some(id), Some(threshold)) =
+                (attribute(input, "id"), attribute(input, "thresholdLevel"))
+            {
+                let threshold = threshold.parse::<i64>().map_err(|_| {
+                    let message = format!("Invalid threshold of input `{}`.", id);
+                    sbml_error(Syntax, message, input).with_identifier(&id)
+                })?;
+                thresholds.insert(id, threshold);
+            }
+        }
+        let default = elements(transition, "defaultTerm")
+            .first()
+            .and_then(|term| attribute(*term, "resultLevel"))
+            .unwrap_or_else(|| "0".to_string())
+            .parse::<u32>()
+            .map_err(|_| {
+                let message = "Invalid default term result level.".to_string();
+                sbml_error(Syntax, message, transition)
+            })?;
+        let mut terms = Vec::new();
+        for term in elements(transition, "functionTerm") {
+            let level = attribute(term, "resultLevel")
+                .and_then(|level| level.parse::<u32>().ok())
+                .ok_or_else(|| {
+                    let message = "Missing or invalid function term result level.".to_string();
+                    sbml_error(Syntax, message, term)
+                })?;
+            let math = term
+                .children()
+                .find(|n| n.tag_name().name() == "math")
+                .and_then(|math| math.children().find(|n| n.is_element()))
+                .ok_or_else(|| {
+                    let message = "Function term is missing a `math` element.".to_string();
+                    sbml_error(Syntax, message, term)
+                })?;
+            let condition = mathml_to_expression(math, &thresholds, &at_least)?;
+            terms.push((level, condition));
+        }
+        for output in elements(transition, "output") {
+            let species = attribute(output, "qualitativeSpecies").ok_or_else(|| {
+                let message = "Output is missing a `qualitativeSpecies`.".to_string();
+                sbml_error(Syntax, message, output)
+            })?;
+            if targets.contains_key(&species) {
+                let message = format!(
+                    "Species `{}` is the output of more than one transition.",
+                    species
+                );
+                return Err(sbml_error(Syntax, message, output).with_identifier(&species));
+            }
+            targets.insert(species, (default, terms.clone()));
+        }
+    }
+
+    let mut functions = Vec::new();
+    for species in &mapping {
+        let max_level = species.max_level;
+        for k in 1..=max_level.max(1) {
+            let variable = &species.variables[(k - 1) as usize];
+            let target_at_least = match targets.get(&species.species) {
+                Some((default, terms)) => target_level_at_least(*default, terms, k),
+                // Species without a transition are constant.
+                None => {
+                    functions.push((variable.clone(), variable.clone()));
+                    continue;
+                }
+            };
+            let function = if max_level <= 1 {
+                target_at_least
+            } else {
+                // Unitary steps: x_bk turns on only from level k-1, and it stays on while
+                // the level is above k.
+                let above = at_least(&species.species, k as i64 + 1)?;
+                let below = at_least(&species.species, k as i64 - 1)?;
+                format!("{} | ({} & ({}))", above, below, target_at_least)
+            };
+            functions.push((variable.clone(), function));
+        }
+    }
+
+    let mut positions = Vec::new();
+    for glyph in elements(document.root(), "speciesGlyph") {
+        let species = attribute(glyph, "species");
+        let position = elements(glyph, "position").first().and_then(|p| {
+            let x = attribute(*p, "x")?.parse::<f64>().ok()?;
+            let y = attribute(*p, "y")?.parse::<f64>().ok()?;
+            Some((x, y))
+        });
+        if let (Some(species), Some((x, y))) = (species, position) {
+            if let Some(species) = mapping.iter().find(|m| m.species == species) {
+                for (k, variable) in species.variables.iter().enumerate() {
+                    positions.push((variable.clone(), (x + 20.0 * k as f64, y)));
+                }
+            }
+        }
+    }
+
+    Ok(BooleanizedModel {
+        mapping,
+        functions,
+        positions,
+    })
+}
+
+/// **(internal)** An SBML conversion error located at the given `node`.
+fn sbml_error(kind: ConversionErrorKind, message: String, node: Node) -> ConversionError {
+    ConversionError::new("sbml", kind, message).at_node(node)
+}
+
+/// **(internal)** The value of the attribute with the given local name (ignoring namespaces).
+fn attribute(node: Node, name: &str) -> Option<String> {
+    node.attributes()
+        .find(|a| a.name() == name)
+        .map(|a| a.value().to_string())
+}
+
+/// **(internal)** All descendants of `node` with the given local name (ignoring namespaces).
+fn elements<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Vec<Node<'a, 'input>> {
+    node.descendants()
+        .filter(|n| n.tag_name().name() == name)
+        .collect()
+}
+
+/// **(internal)** The expression which is true when the target level given by the function
+/// terms (the first satisfied term wins, `default` is used if none is satisfied) is at
+/// least `k`.
+fn target_level_at_least(default: u32, terms: &[(u32, String)], k: u32) -> String {
+    let mut clauses = Vec::new();
+    for (i, (level, condition)) in terms.iter().enumerate() {
+        if *level >= k {
+            let mut clause = vec![format!("({})", condition)];
+            clause.extend(terms[..i].iter().map(|(_, c)| format!("!({})", c)));
+            clauses.push(clause.join(" & "));
+        }
+    }
+    if default >= k {
+        let mut clause = terms
+            .iter()
+            .map(|(_, c)| format!("!({})", c))
+            .collect::<Vec<_>>();
+        if clause.is_empty() {
+            clause.push("true".to_string());
+        }
+        clauses.push(clause.join(" & "));
+    }
+    if clauses.is_empty() {
+        "false".to_string()
+    } else {
+        clauses
+            .into_iter()
+            .map(|c| format!("({})", c))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+/// **(internal)** Translate a MathML condition into a Boolean expression over threshold
+/// variables.
+fn mathml_to_expression(
+    node: Node,
+    thresholds: &HashMap<String, i64>,
+    at_least: &dyn Fn(&str, i64) -> Result<String, ConversionError>,
+) -> Result<String, ConversionError> {
+    match node.tag_name().name() {
+        "true" => return Ok("true".to_string()),
+        "false" => return Ok("false".to_string()),
+        "apply" => (),
+        other => {
+            let message = format!("Unsupported MathML element `{}`.", other);
+            return Err(sbml_error(UnsupportedFeature, message, node));
+        }
+    }
+    let mut children = node.children().filter(|n| n.is_element());
+    let operator = children.next().ok_or_else(|| {
+        let message = "Empty MathML `apply` element.".to_string();
+        sbml_error(Syntax, message, node)
+    })?;
+    let arguments = children.collect::<Vec<_>>();
+    let operator = operator.tag_name().name();
+    match operator {
+        "and" | "or" | "xor" | "implies" => {
+            let symbol = match operator {
+                "and" => " & ",
+                "or" => " | ",
+                "xor" => " ^ ",
+                _ => " => ",
+            };
+            let arguments = arguments
+                .into_iter()
+                .map(|a| mathml_to_expression(a, thresholds, at_least).map(|e| format!("({})", e)))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(arguments.join(symbol))
+        }
+        "not" => {
+            let inner = arguments.first().ok_or_else(|| {
+                let message = "MathML `not` without an argument.".to_string();
+                sbml_error(Syntax, message, node)
+            })?;
+            Ok(format!(
+                "!({})",
+                mathml_to_expression(*inner, thresholds, at_least)?
+            ))
+        }
+        "eq" | "neq" | "lt" | "leq" | "gt" | "geq" => {
+            if arguments.len() != 2 {
+                let message = format!("MathML `{}` requires two arguments.", operator);
+                return Err(sbml_error(Syntax, message, node));
+            }
+            let operand = |node: Node| -> Result<Operand, ConversionError> {
+                let text = node.text().unwrap_or("").trim().to_string();
+                match node.tag_name().name() {
+                    "cn" => text.parse::<i64>().map(Operand::Level).map_err(|_| {
+                        let message = format!("Invalid MathML number `{}`.", text);
+                        sbml_error(Syntax, message, node)
+                    }),
+                    "ci" => Ok(match thresholds.get(&text) {
+                        Some(threshold) => Operand::Level(*threshold),
+                        None => Operand::Species(text),
+                    }),
+                    other => {
+                        let message = format!("Unsupported MathML operand `{}`.", other);
+                        Err(sbml_error(UnsupportedFeature, message, node))
+                    }
+                }
+            };
+            let at_least =
+                |species: &str, k: i64| at_least(species, k).map_err(|e| e.at_node(node));
+            // Normalize to `species <op> level`.
+            let (species, level, operator) = match (operand(arguments[0])?, operand(arguments[1])?)
+            {
+                (Operand::Species(s), Operand::Level(l)) => (s, l, operator),
+                (Operand::Level(l), Operand::Species(s)) => {
+                    let flipped = match operator {
+                        "lt" => "gt",
+                        "leq" => "geq",
+                        "gt" => "lt",
+                        "geq" => "leq",
+                        other => other,
+                    };
+                    (s, l, flipped)
+                }
+                _ => {
+                    let message = "MathML comparison must compare a species to a level.";
+                    return Err(sbml_error(Syntax, message.to_string(), node));
+                }
+            };
+            Ok(match operator {
+                "geq" => at_least(&species, level)?,
+                "gt" => at_least(&species, level + 1)?,
+                "leq" => format!("!{}", at_least(&species, level + 1)?),
+                "lt" => format!("!{}", at_least(&species, level)?),
+                "eq" => format!(
+                    "({} & !{})",
+                    at_least(&species, level)?,
+                    at_least(&species, level + 1)?
+                ),
+                _ => format!(
+                    "!({} & !{})",
+                    at_least(&species, level)?,
+                    at_least(&species, level + 1)?
+                ),
+            })
+        }
+        other => {
+            let message = format!("Unsupported MathML operator `{}`.", other);
+            Err(sbml_error(UnsupportedFeature, message, node))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversions::_impl_sbml_booleanize::{booleanize_sbml, booleanized_aeon};
+    use crate::conversions::ConversionErrorKind;
+    use crate::Conversions;
+    use biodivine_lib_param_bn::BooleanNetwork;
+
+    #[test]
+    fn booleanize_multi_valued_species() {
+        // `x` (levels 0..2) is activated by `y` to level 1, and by itself to level 2.
+        let sbml = r#"<sbml xmlns="http://www.sbml.org/sbml/level3/version1/core"
+              xmlns:qual="http://www.sbml.org/sbml/level3/version1/qual/version1">
+            <model>
+              <qual:listOfQualitativeSpecies>
+                <qual:qualitativeSpecies qual:id="x" qual:maxLevel="2"/>
+                <qual:qualitativeSpecies qual:id="y" qual:maxLevel="1"/>
+              </qual:listOfQualitativeSpecies>
+              <qual:listOfTransitions>
+                <qual:transition qual:id="t_x">
+                  <qual:listOfInputs>
+                    <qual:input qual:id="th" qual:qualitativeSpecies="x" qual:thresholdLevel="1"/>
+                  </qual:listOfInputs>
+                  <qual:listOfOutputs><qual:output qual:qualitativeSpecies="x"/></qual:listOfOutputs>
+                  <qual:listOfFunctionTerms>
+                    <qual:defaultTerm qual:resultLevel="0"/>
+                    <qual:functionTerm qual:resultLevel="2">
+                      <math xmlns="http://www.w3.org/1998/Math/MathML">
+                        <apply><geq/><ci>x</ci><ci>th</ci></apply>
+                      </math>
+                    </qual:functionTerm>
+                    <qual:functionTerm qual:resultLevel="1">
+                      <math xmlns="http://www.w3.org/1998/Math/MathML">
+                        <apply><eq/><cn type="integer">1</cn><ci>y</ci></apply>
+                      </math>
+                    </qual:functionTerm>
+                  </qual:listOfFunctionTerms>
+                </qual:transition>
+              </qual:listOfTransitions>
+            </model>
+            </sbml>"#;
+        let model = booleanize_sbml(sbml).unwrap();
+        assert_eq!(vec!["x_b1", "x_b2"], model.mapping[0].variables);
+        assert_eq!(vec!["y"], model.mapping[1].variables);
+        assert_eq!(
+            vec![
+                (
+                    "x_b1".to_string(),
+                    "x_b2 | (true & (((x_b1)) | (((y & !false)) & !(x_b1))))".to_string()
+                ),
+                (
+                    "x_b2".to_string(),
+                    "false | (x_b1 & (((x_b1))))".to_string()
+                ),
+                ("y".to_string(), "y".to_string()),
+            ],
+            model.functions
+        );
+    }
+
+    #[test]
+    fn booleanize_with_name_clashes() {
+        // The threshold variables of `x` would clash with the species `x_b1`.
+        let transition = r#"<qual:transition>
+                  <qual:listOfOutputs><qual:output qual:qualitativeSpecies="x"/></qual:listOfOutputs>
+                  <qual:listOfFunctionTerms>
+                    <qual:defaultTerm qual:resultLevel="0"/>
+                    <qual:functionTerm qual:resultLevel="2">
+                      <math xmlns="http://www.w3.org/1998/Math/MathML">
+                        <apply><eq/><cn type="integer">1</cn><ci>x_b1</ci></apply>
+                      </math>
+                    </qual:functionTerm>
+                  </qual:listOfFunctionTerms>
+                </qual:transition>"#;
+        let sbml = format!(
+            r#"<sbml xmlns="http://www.sbml.org/sbml/level3/version1/core"
+              xmlns:qual="http://www.sbml.org/sbml/level3/version1/qual/version1">
+            <model>
+              <qual:listOfQualitativeSpecies>
+                <qual:qualitativeSpecies qual:id="x" qual:maxLevel="2"/>
+                <qual:qualitativeSpecies qual:id="x_b1" qual:maxLevel="1"/>
+              </qual:listOfQualitativeSpecies>
+              <qual:listOfTransitions>
+                {}
+              </qual:listOfTransitions>
+            </model>
+            </sbml>"#,
+            transition
+        );
+        let result = booleanized_aeon(&sbml).unwrap();
+        assert_eq!(vec!["x_2_b1", "x_2_b2"], result.mapping[0].variables);
+        assert_eq!(vec!["x_b1"], result.mapping[1].variables);
+        let network = BooleanNetwork::try_from(result.model.as_str()).unwrap();
+        assert_eq!(3, network.num_vars());
+        assert!(network.as_graph().find_variable("x_2_b2").is_some());
+
+        // Two transitions with the same output are rejected.
+        let duplicate = sbml.replace(transition, &format!("{}\n{}", transition, transition));
+        let error = Conversions::sbml_to_aeon_booleanized(&duplicate).unwrap_err();
+        assert_eq!(ConversionErrorKind::Syntax, error.kind);
+        assert_eq!(Some("x".to_string()), error.identifier);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::conversions::ConversionErrorKind::Syntax;
+use crate::conversions::{instantiate_network, unique_names, ConversionError};
+use crate::Conversions;
+use biodivine_lib_param_bn::{BinaryOp, FnUpdate};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Identifiers which have a special meaning in NuSMV and cannot be used as variable names.
+const SMV_RESERVED: &str = "MODULE DEFINE MDEFINE CONSTANTS VAR IVAR FROZENVAR INIT TRANS \
+    INVAR SPEC CTLSPEC LTLSPEC PSLSPEC COMPUTE NAME INVARSPEC FAIRNESS JUSTICE COMPASSION ISA \
+    ASSIGN CONSTRAINT SIMPWFF CTLWFF LTLWFF PSLWFF COMPWFF IN MIN MAX MIRROR PRED PREDICATES \
+    process array of boolean integer real word word1 bool signed unsigned extend resize sizeof \
+    uwconst swconst EX AX EF AF EG AG E F O G H X Y Z A U S V T BU EBF ABF EBG ABG case esac \
+    mod next init union in xor xnor self count abs max min TRUE FALSE";
+
+/// The name of the input variable which selects the updated variable.
+const SMV_SCHEDULER: &str = "__update";
+
+#[wasm_bindgen]
+impl Conversions {
+    /// Convert a Boolean network model encoded as an `.aeon` string into a NuSMV model
+    /// with asynchronous semantics.
+    ///
+    /// In every step, the input variable `__update` non-deterministically selects one
+    /// network variable which is updated, while all other variables keep their value.
+    /// All states are initial. Networks with uninterpreted parameters or implicit update
+    /// functions are instantiated using a witness network.
+    ///
+    /// Variables whose names are not valid NuSMV identifiers (e.g. `A` or `next`) are
+    /// renamed using a `v_` prefix (and a numeric suffix if the new name is already taken).
+    /// The optional `specs` are appended to the model, one spec
+    /// per line. Lines without a `CTLSPEC`, `LTLSPEC`, `SPEC` or `INVARSPEC` keyword
+    /// are treated as `CTLSPEC`.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    pub fn aeon_to_smv(
+        aeon_string: &str,
+        specs: Option<String>,
+    ) -> Result<String, ConversionError> {
+        let error = ConversionError::in_format("aeon", Syntax, aeon_string);
+        let network = instantiate_network(aeon_string).map_err(error)?;
+        let names = network
+            .variables()
+            .map(|v| network.get_variable_name(v).clone())
+            .collect::<Vec<_>>();
+        let names = unique_names(&names, &[], smv_name);
+        let functions = network
+            .variables()
+            .map(|v| {
+                let function = network.get_update_function(v).as_ref().unwrap();
+                smv_function(&names, function)
+            })
+            .collect::<Vec<_>>();
+        let specs = specs.unwrap_or_default();
+        let specs = specs
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        Ok(write_smv_module(&names, &functions, &specs))
+    }
+}
+
+/// **(internal)** Write the asynchronous NuSMV module for the given variables and their
+/// (already translated) update functions.
+pub(crate) fn write_smv_module(names: &[String], functions: &[String], specs: &[&str]) -> String {
+    let mut smv = String::from("MODULE main\n\nIVAR\n");
+    // Scheduler values share the namespace with variables, so they must not clash.
+    let choices = unique_names(names, names, |name| format!("u_{}", name));
+    smv += format!("  {} : {{{}}};\n\nVAR\n", SMV_SCHEDULER, choices.join(", ")).as_str();
+    for name in names {
+        smv += format!("  {} : boolean;\n", name).as_str();
+    }
+    smv += "\nASSIGN\n";
+    for ((name, choice), function) in names.iter().zip(&choices).zip(functions) {
+        smv += format!(
+            "  next({}) := case\n    {} = {} : {};\n    TRUE : {};\n  esac;\n",
+            name, SMV_SCHEDULER, choice, function, name
+        )
+        .as_str();
+    }
+    if !specs.is_empty() {
+        smv += "\n";
+    }
+    for spec in specs {
+        let has_keyword = ["CTLSPEC", "LTLSPEC", "SPEC", "INVARSPEC"]
+            .iter()
+            .any(|keyword| spec.starts_with(keyword));
+        if has_keyword {
+            smv += format!("{}\n", spec).as_str();
+        } else {
+            smv += format!("CTLSPEC {}\n", spec).as_str();
+        }
+    }
+    smv
+}
+
+/// **(internal)** Make the given variable name a valid NuSMV identifier. The result can
+/// clash with other names, so it should be used through `unique_names`.
+pub(crate) fn smv_name(name: &str) -> String {
+    let starts_with_digit = name.chars().next().map(|c| c.is_ascii_digit()) == Some(true);
+    let is_reserved = SMV_RESERVED.split_whitespace().any(|word| word == name);
+    let name = name.replace(['{', '}'], "_");
+    if starts_with_digit || is_reserved || name == SMV_SCHEDULER {
+        format!("v_{}", name)
+    } else {
+        name
+    }
+}
+
+/// **(internal)** Write a parameter-free update function using the NuSMV operators.
+fn smv_function(names: &[String], function: &FnUpdate) -> String {
+    match function {
+        FnUpdate::Const(value) => if *value { "TRUE" } else { "FALSE" }.to_string(),
+        FnUpdate::Var(var) => names[var.to_index()].clone(),
+        FnUpdate::Param(_, _) => unreachable!("The network is instantiated."),
+        FnUpdate::Not(inner) => format!("!{}", smv_function(names, inner)),
+        FnUpdate::Binary(op, left, right) => {
+            let op = match op {
+                BinaryOp::And => "&",
+                BinaryOp::Or => "|",
+                BinaryOp::Xor => "xor",
+                BinaryOp::Imp => "->",
+                BinaryOp::Iff => "<->",
+            };
+            format!(
+                "({} {} {})",
+                smv_function(names, left),
+                op,
+                smv_function(names, right)
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversions::_impl_smv::{smv_name, write_smv_module};
+    use crate::Conversions;
+
+    #[test]
+    fn asynchronous_smv_module() {
+        assert_eq!("v_A", smv_name("A"));
+        assert_eq!("v_1x", smv_name("1x"));
+        assert_eq!("CycD", smv_name("CycD"));
+
+        let names = vec!["a".to_string(), "v_A".to_string()];
+        let functions = vec!["!v_A".to_string(), "TRUE".to_string()];
+        let smv = write_smv_module(&names, &functions, &["AG EF a", "LTLSPEC G F v_A"]);
+        assert!(smv.contains("  __update : {u_a, u_v_A};\n"));
+        assert!(
+            smv.contains("  next(a) := case\n    __update = u_a : !v_A;\n    TRUE : a;\n  esac;\n")
+        );
+        assert!(smv.ends_with("CTLSPEC AG EF a\nLTLSPEC G F v_A\n"));
+
+        // Renamed variables and scheduler values must not clash with existing names.
+        let aeon = "A -> v_A\nv_A -| A\n$A: !v_A\n$v_A: A\n";
+        let smv = Conversions::aeon_to_smv(aeon, None).unwrap();
+        assert!(smv.contains("  __update : {u_v_A_2, u_v_A};\n"));
+        assert!(smv.contains("  next(v_A_2) := case\n    __update = u_v_A_2 : !v_A;\n"));
+        let aeon = "a -> u_a\nu_a -| a\n$a: !u_a\n$u_a: a\n";
+        let smv = Conversions::aeon_to_smv(aeon, None).unwrap();
+        assert!(smv.contains("  __update : {u_a_2, u_u_a};\n"));
+        assert!(smv.contains("  next(a) := case\n    __update = u_a_2 : !u_a;\n"));
+    }
+}
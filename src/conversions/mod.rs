@@ -0,0 +1,315 @@
+use crate::boolean_network::BooleanNetworkModel;
+use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+use biodivine_lib_param_bn::BooleanNetwork;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use _impl_bnet::{
+    declare_regulations, read_bnet_annotations, write_bnet_annotations, BnetAnnotations,
+};
+use _impl_conversion_error::check_layout;
+use _impl_metadata::{add_sbml_notes, read_sbml_notes};
+pub use _impl_metadata::{read_metadata, write_metadata};
+use ConversionErrorKind::{Syntax, UnsupportedFeature};
+
+/// **(internal)** The `# aeon:` comment extension of the `.bnet` format.
+mod _impl_bnet;
+/// **(internal)** Conversion from and to the R BoolNet format.
+mod _impl_boolnet;
+/// **(internal)** Construction and location of `ConversionError` objects.
+mod _impl_conversion_error;
+/// **(internal)** Import of GINsim `.ginml` models.
+mod _impl_ginml;
+/// **(internal)** Export into the MaBoSS `.bnd` and `.cfg` format.
+mod _impl_maboss;
+/// **(internal)** Reading and writing of model metadata in `.aeon` and `.sbml` files.
+mod _impl_metadata;
+/// **(internal)** Export of the asynchronous dynamics as a `.pnml` Petri net.
+mod _impl_pnml;
+/// **(internal)** Conversion from and to the PyBoolNet "primes" JSON format.
+mod _impl_primes;
+/// **(internal)** Import of multi-valued SBML-qual models using threshold variables.
+mod _impl_sbml_booleanize;
+/// **(internal)** Export into the NuSMV model checker format.
+mod _impl_smv;
+
+/// A utility object that jointly covers conversion methods for various Boolean network formats,
+/// including some information about the layout of network nodes.
+///
+/// You should never instantiate this object. Instead, just use the static methods it provides.
+#[wasm_bindgen]
+pub struct Conversions {
+    _dummy: (), // Private field prevents instantiation.
+}
+
+/// The category of a `ConversionError`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConversionErrorKind {
+    Syntax,
+    UnknownVariable,
+    UnsupportedFeature,
+    InvalidLayout,
+}
+
+/// An error thrown by the methods of `Conversions`.
+///
+/// The `format` is the format of the input (e.g. `aeon`, `sbml` or `bnet`). If the problem
+/// can be traced back to the input, `line` and `column` (both starting at 1) point to it.
+/// The `identifier` is the name of the offending variable or element, if known.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConversionError {
+    #[wasm_bindgen(readonly)]
+    pub format: String,
+    #[wasm_bindgen(readonly)]
+    pub kind: ConversionErrorKind,
+    #[wasm_bindgen(readonly)]
+    pub message: String,
+    #[wasm_bindgen(readonly)]
+    pub line: Option<u32>,
+    #[wasm_bindgen(readonly)]
+    pub column: Option<u32>,
+    #[wasm_bindgen(readonly)]
+    pub identifier: Option<String>,
+}
+
+/// Free-text annotations of a variable or a regulation.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Annotations {
+    pub notes: Vec<String>,
+    pub references: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+/// Model metadata stored in the comments of an `.aeon` file. Variables are identified
+/// by name and regulations by a `(source, target)` pair of names.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AeonMetadata {
+    pub name: String,
+    pub description: String,
+    pub variables: BTreeMap<String, Annotations>,
+    pub regulations: BTreeMap<(String, String), Annotations>,
+}
+
+#[wasm_bindgen]
+impl Conversions {
+    /// Convert a Boolean network model encoded as an `.sbml` string into a model encoded
+    /// as an `.aeon` string.
+    ///
+    /// The conversion preserves the node layout present in the `.sbml` file, as well as
+    /// the model name and the notes of the model, its species and transition inputs.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    pub fn sbml_to_aeon(sbml_string: &str) -> Result<String, ConversionError> {
+        // Check the XML syntax first, such that the error has an exact location.
+        roxmltree::Document::parse(sbml_string)
+            .map_err(|e| ConversionError::from_xml("sbml", e))?;
+        let (model, layout) = BooleanNetwork::try_from_sbml(sbml_string)
+            .map_err(ConversionError::in_format("sbml", Syntax, sbml_string))?;
+        let mut model_string = format!("{}", model); // convert back to aeon
+        model_string += "\n";
+        for (var, (x, y)) in layout {
+            model_string += format!("#position:{}:{},{}\n", var, x, y).as_str();
+        }
+        model_string += write_metadata(&read_sbml_notes(sbml_string)).as_str();
+        Ok(model_string)
+    }
+
+    /// Convert a Boolean network model encoded as a `.bnet` string into a model encoded
+    /// as an `.aeon` string.
+    ///
+    /// A plain `.bnet` model has no layout or regulation monotonicity information, so you
+    /// need to then compute some layout manually and infer monotonicity/essentiality as well.
+    /// However, if the file contains the `# aeon:` comments written by `aeon_to_bnet`, the
+    /// declared regulations, layout and model metadata are restored.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    pub fn bnet_to_aeon(bnet_string: &str) -> Result<String, ConversionError> {
+        let error = ConversionError::in_format("bnet", Syntax, bnet_string);
+        let network = BooleanNetwork::try_from_bnet(bnet_string).map_err(&error)?;
+        let annotations = read_bnet_annotations(bnet_string);
+        if annotations == BnetAnnotations::default() {
+            return Ok(network.to_string());
+        }
+        check_layout("bnet", bnet_string, "# aeon:position:")?;
+        let network = declare_regulations(&network, &annotations).map_err(&error)?;
+        Ok(format!("{}\n{}", network, annotations.comments))
+    }
+
+    /// Convert a Boolean network model encoded as an `.aeon` string into a model encoded
+    /// as an `.sbml` string.
+    ///
+    /// The conversion preserves the node layout present in the `.aeon` file. The model
+    /// name, description and variable and regulation annotations are stored as SBML notes.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    #[wasm_bindgen]
+    pub fn aeon_to_sbml(aeon_string: &str) -> Result<String, ConversionError> {
+        let error = ConversionError::in_format("aeon", Syntax, aeon_string);
+        let network = BooleanNetwork::try_from(aeon_string).map_err(error)?;
+        check_layout("aeon", aeon_string, "#position:")?;
+        let layout = read_layout(aeon_string);
+        let sbml_string = network.to_sbml(Some(&layout));
+        let metadata = read_metadata(aeon_string);
+        Ok(add_sbml_notes(&sbml_string, &metadata))
+    }
+
+    /// Convert a Boolean network model encoded as an `.aeon` string into a model encoded
+    /// as a `.bnet` string.
+    ///
+    /// There is no layout or regulation monotonicity information in a `.bnet` model, so these
+    /// are written as `# aeon:regulation:` and `# aeon:position:` comments (together with
+    /// other model metadata). Other tools ignore these comments, but `bnet_to_aeon` uses
+    /// them to restore the original model.
+    ///
+    /// When the model is not valid, a `ConversionError` is thrown.
+    #[wasm_bindgen]
+    pub fn aeon_to_bnet(aeon_string: &str) -> Result<String, ConversionError> {
+        let error = ConversionError::in_format("aeon", Syntax, aeon_string);
+        let unsupported = ConversionError::in_format("aeon", UnsupportedFeature, aeon_string);
+        let network = BooleanNetwork::try_from(aeon_string).map_err(error)?;
+        check_layout("aeon", aeon_string, "#position:")?;
+        let bnet_string = network.to_bnet(false).map_err(unsupported)?;
+        Ok(format!(
+            "{}\n{}",
+            bnet_string.trim_end(),
+            write_bnet_annotations(&network, aeon_string)
+        ))
+    }
+
+    /// Export the regulatory graph of an `.aeon` model in the Graphviz `.dot` format.
+    ///
+    /// See `BooleanNetworkModel::to_dot` for details. When the model is not valid,
+    /// a `ConversionError` is thrown.
+    pub fn aeon_to_dot(aeon_string: &str) -> Result<String, ConversionError> {
+        let error = ConversionError::in_format("aeon", Syntax, aeon_string);
+        Ok(BooleanNetworkModel::from_aeon(aeon_string)
+            .map_err(error)?
+            .to_dot())
+    }
+
+    /// Export the regulatory graph of an `.aeon` model in the `.graphml` format.
+    ///
+    /// See `BooleanNetworkModel::to_graphml` for details. When the model is not valid,
+    /// a `ConversionError` is thrown.
+    pub fn aeon_to_graphml(aeon_string: &str) -> Result<String, ConversionError> {
+        let error = ConversionError::in_format("aeon", Syntax, aeon_string);
+        Ok(BooleanNetworkModel::from_aeon(aeon_string)
+            .map_err(error)?
+            .to_graphml())
+    }
+
+    /// Export the regulatory graph of an `.aeon` model as a Cytoscape.js JSON string.
+    ///
+    /// See `BooleanNetworkModel::to_cytoscape` for details. When the model is not valid,
+    /// a `ConversionError` is thrown.
+    pub fn aeon_to_cytoscape(aeon_string: &str) -> Result<String, ConversionError> {
+        let error = ConversionError::in_format("aeon", Syntax, aeon_string);
+        Ok(BooleanNetworkModel::from_aeon(aeon_string)
+            .map_err(error)?
+            .to_cytoscape())
+    }
+
+    /// Convert a regulatory graph in the `.graphml` format into a model encoded
+    /// as an `.aeon` string. All update functions are left implicit.
+    ///
+    /// See `BooleanNetworkModel::from_graphml` for details. When the graph is not valid,
+    /// a `ConversionError` is thrown.
+    pub fn graphml_to_aeon(graphml_string: &str) -> Result<String, ConversionError> {
+        Ok(BooleanNetworkModel::from_graphml(graphml_string)?.to_aeon())
+    }
+
+    /// Convert a regulatory graph in the Cytoscape.js JSON format into a model encoded
+    /// as an `.aeon` string. All update functions are left implicit.
+    ///
+    /// See `BooleanNetworkModel::from_cytoscape` for details. When the graph is not valid,
+    /// a `ConversionError` is thrown.
+    pub fn cytoscape_to_aeon(json_string: &str) -> Result<String, ConversionError> {
+        Ok(BooleanNetworkModel::from_cytoscape(json_string)?.to_aeon())
+    }
+
+    /// Deprecated: Use only for backwards-compatibility reasons.
+    #[wasm_bindgen]
+    pub fn aeon_to_sbml_instantiated(aeon_string: &str) -> Result<String, ConversionError> {
+        let graph = BooleanNetwork::try_from(aeon_string)
+            .and_then(|bn| SymbolicAsyncGraph::new(&bn))
+            .map_err(ConversionError::in_format("aeon", Syntax, aeon_string))?;
+        let witness = graph.pick_witness(graph.unit_colors());
+        let layout = read_layout(aeon_string);
+        let sbml_string = witness.to_sbml(Some(&layout));
+        let metadata = read_metadata(aeon_string);
+        Ok(add_sbml_notes(&sbml_string, &metadata))
+    }
+}
+
+/// Parse the given aeon model and make sure it has no uninterpreted parameters or implicit
+/// update functions. If it has some, a witness network (the same one that would be used
+/// by `aeon_to_sbml_instantiated`) is picked instead. In both cases, an error is returned
+/// if the update functions violate the declared regulations.
+pub(crate) fn instantiate_network(aeon_string: &str) -> Result<BooleanNetwork, String> {
+    let network = BooleanNetwork::try_from(aeon_string)?;
+    // Building the graph also checks that the functions satisfy the regulation constraints.
+    let graph = SymbolicAsyncGraph::new(&network)?;
+    let is_instantiated = network.num_parameters() == 0
+        && network
+            .variables()
+            .all(|v| network.get_update_function(v).is_some());
+    if is_instantiated {
+        Ok(network)
+    } else {
+        Ok(graph.pick_witness(graph.unit_colors()))
+    }
+}
+
+/// **(internal)** Translate the given `names` into identifiers of another format using
+/// `rename`. Names which `rename` keeps unchanged also keep their identifier, the remaining
+/// ones get a numeric suffix (`_2`, `_3`, ...) until they differ from all other identifiers
+/// and from the `reserved` ones.
+pub(crate) fn unique_names<F: Fn(&str) -> String>(
+    names: &[String],
+    reserved: &[String],
+    rename: F,
+) -> Vec<String> {
+    let renamed = names.iter().map(|name| rename(name)).collect::<Vec<_>>();
+    let mut used = reserved.iter().cloned().collect::<HashSet<_>>();
+    for (name, identifier) in names.iter().zip(&renamed) {
+        if name == identifier {
+            used.insert(identifier.clone());
+        }
+    }
+    names
+        .iter()
+        .zip(renamed)
+        .map(|(name, identifier)| {
+            if *name == identifier && !reserved.contains(name) {
+                return identifier;
+            }
+            let unique = std::iter::once(identifier.clone())
+                .chain((2..).map(|i| format!("{}_{}", identifier, i)))
+                .find(|candidate| !used.contains(candidate))
+                .unwrap();
+            used.insert(unique.clone());
+            unique
+        })
+        .collect()
+}
+
+/// Try to read the model layout metadata from the given aeon file.
+pub fn read_layout(aeon_string: &str) -> HashMap<String, (f64, f64)> {
+    let re = Regex::new(r"^\s*#position:(?P<var>[a-zA-Z0-9_]+):(?P<x>.+?),(?P<y>.+?)\s*$").unwrap();
+    let mut layout = HashMap::new();
+    for line in aeon_string.lines() {
+        if let Some(captures) = re.captures(line) {
+            let var = captures["var"].to_string();
+            let x = captures["x"].parse::<f64>();
+            let y = captures["y"].parse::<f64>();
+            if let (Ok(x), Ok(y)) = (x, y) {
+                layout.insert(var, (x, y));
+            }
+        }
+    }
+    layout
+}
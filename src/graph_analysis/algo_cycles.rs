@@ -0,0 +1,190 @@
+use crate::graph_analysis::{Sign, SignedGraph};
+
+/// An elementary cycle of a `SignedGraph`.
+///
+/// The cycle starts in its smallest vertex and there is an edge from every vertex to
+/// the next one (and from the last vertex back to the first one).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub vertices: Vec<usize>,
+    pub sign: Sign,
+}
+
+/// **(internal)** The state of Johnson's search for cycles that start in `start`.
+///
+/// A vertex is `blocked` while it is on the current path, or while it cannot reach `start`
+/// without visiting the path. Once a vertex is unblocked, all vertices in its `blocked_by`
+/// list are unblocked as well.
+struct CycleSearch<'a> {
+    graph: &'a SignedGraph,
+    component: Vec<usize>,
+    start: usize,
+    path: Vec<usize>,
+    sign: Vec<Sign>,
+    blocked: Vec<bool>,
+    blocked_by: Vec<Vec<usize>>,
+    max_length: usize,
+    max_count: usize,
+    cycles: Vec<Cycle>,
+    is_complete: bool,
+}
+
+impl SignedGraph {
+    /// Enumerate the elementary cycles of this graph which have at most `max_length` vertices.
+    ///
+    /// The enumeration stops once `max_count` cycles are found. The second value of the
+    /// result is `true` if the enumeration is complete, i.e. it was not stopped by the
+    /// `max_count` limit.
+    ///
+    /// The cycles are enumerated using Johnson's algorithm. Every cycle is only searched
+    /// for within its strongly connected component, and cycles are returned ordered by
+    /// their smallest vertex.
+    pub fn elementary_cycles(&self, max_length: usize, max_count: usize) -> (Vec<Cycle>, bool) {
+        let mut component = vec![0; self.num_vertices()];
+        for (i, scc) in self.strongly_connected_components().into_iter().enumerate() {
+            for v in scc {
+                component[v] = i;
+            }
+        }
+        let mut search = CycleSearch {
+            graph: self,
+            component,
+            start: 0,
+            path: Vec::new(),
+            sign: Vec::new(),
+            blocked: vec![false; self.num_vertices()],
+            blocked_by: vec![Vec::new(); self.num_vertices()],
+            max_length,
+            max_count,
+            cycles: Vec::new(),
+            is_complete: true,
+        };
+        for start in 0..self.num_vertices() {
+            search.start = start;
+            search.blocked.fill(false);
+            search.blocked_by.iter_mut().for_each(Vec::clear);
+            search.path.push(start);
+            search.sign.push(Sign::Positive);
+            search.extend(start);
+            search.sign.pop();
+            search.path.pop();
+            if !search.is_complete {
+                return (search.cycles, false);
+            }
+        }
+        (search.cycles, true)
+    }
+}
+
+impl CycleSearch<'_> {
+    /// Extend the current path (which ends in `vertex`) through all successors of `vertex`.
+    /// Returns `true` if a cycle was found, or if the path could not be extended because
+    /// of the length limit. Otherwise, `vertex` stays blocked.
+    fn extend(&mut self, vertex: usize) -> bool {
+        self.blocked[vertex] = true;
+        let mut found = false;
+        for (successor, edge_sign) in self.graph.successors(vertex) {
+            let (successor, edge_sign) = (*successor, *edge_sign);
+            if successor < self.start || self.component[successor] != self.component[self.start] {
+                continue;
+            }
+            let path_sign = self.sign.last().unwrap().compose(edge_sign);
+            if successor == self.start {
+                if self.cycles.len() >= self.max_count {
+                    self.is_complete = false;
+                    return true;
+                }
+                self.cycles.push(Cycle {
+                    vertices: self.path.clone(),
+                    sign: path_sign,
+                });
+                found = true;
+            } else if self.path.len() >= self.max_length {
+                // Cycles through `successor` might exist, they are just too long here.
+                found = true;
+            } else if !self.blocked[successor] {
+                self.path.push(successor);
+                self.sign.push(path_sign);
+                found |= self.extend(successor);
+                self.sign.pop();
+                self.path.pop();
+                if !self.is_complete {
+                    return true;
+                }
+            }
+        }
+        if found {
+            self.unblock(vertex);
+        } else {
+            for (successor, _) in self.graph.successors(vertex) {
+                if !self.blocked_by[*successor].contains(&vertex) {
+                    self.blocked_by[*successor].push(vertex);
+                }
+            }
+        }
+        found
+    }
+
+    /// Unblock `vertex` and (recursively) all vertices which were blocked because of it.
+    fn unblock(&mut self, vertex: usize) {
+        self.blocked[vertex] = false;
+        for v in std::mem::take(&mut self.blocked_by[vertex]) {
+            if self.blocked[v] {
+                self.unblock(v);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph_analysis::algo_cycles::Cycle;
+    use crate::graph_analysis::{Sign, SignedGraph};
+
+    #[test]
+    fn signed_elementary_cycles() {
+        let mut graph = SignedGraph::new(vec![0, 1, 2]);
+        graph.add_edge(0, 1, Sign::Negative);
+        graph.add_edge(1, 0, Sign::Negative);
+        graph.add_edge(1, 2, Sign::Positive);
+        graph.add_edge(2, 0, Sign::Negative);
+        graph.add_edge(2, 2, Sign::Unknown);
+
+        let (cycles, complete) = graph.elementary_cycles(usize::MAX, usize::MAX);
+        assert!(complete);
+        let expected = vec![
+            Cycle {
+                vertices: vec![0, 1],
+                sign: Sign::Positive,
+            },
+            Cycle {
+                vertices: vec![0, 1, 2],
+                sign: Sign::Positive,
+            },
+            Cycle {
+                vertices: vec![2],
+                sign: Sign::Unknown,
+            },
+        ];
+        assert_eq!(expected, cycles);
+
+        let (cycles, complete) = graph.elementary_cycles(2, usize::MAX);
+        assert!(complete);
+        assert_eq!(2, cycles.len());
+        let (cycles, complete) = graph.elementary_cycles(usize::MAX, 1);
+        assert!(!complete);
+        assert_eq!(1, cycles.len());
+
+        // A complete graph on four vertices has 6 + 8 + 6 elementary cycles.
+        let mut graph = SignedGraph::new(vec![0, 1, 2, 3]);
+        for v in 0..4 {
+            for u in (0..4).filter(|u| *u != v) {
+                graph.add_edge(v, u, Sign::Positive);
+            }
+        }
+        let (cycles, complete) = graph.elementary_cycles(usize::MAX, usize::MAX);
+        assert!(complete);
+        assert_eq!(20, cycles.len());
+        assert_eq!(14, graph.elementary_cycles(3, usize::MAX).0.len());
+    }
+}
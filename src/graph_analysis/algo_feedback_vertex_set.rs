@@ -0,0 +1,205 @@
+use crate::graph_analysis::algo_cycles::Cycle;
+use crate::graph_analysis::SignedGraph;
+
+/// The maximal number of cycles examined by the exact hitting set search before it gives up
+/// and returns the best set found so far.
+const SEARCH_BUDGET: usize = 10_000_000;
+
+/// **(internal)** The state of the branch-and-bound hitting set search. The `cycles` are
+/// sorted by length and `hits[i]` counts the selected vertices of the `i`-th cycle, such that
+/// the cycles which are not hit yet do not have to be recomputed in every step.
+struct HittingSetSearch<'a> {
+    cycles: Vec<&'a Cycle>,
+    cycles_of: Vec<Vec<usize>>,
+    hits: Vec<usize>,
+    current: Vec<usize>,
+    best: Vec<usize>,
+    budget: usize,
+}
+
+impl SignedGraph {
+    /// Compute a minimum set of vertices that intersects every cycle in `cycles`.
+    ///
+    /// The search starts with a greedy solution and then tries to improve it using
+    /// a branch-and-bound search. The second value of the result is `true` if the search
+    /// finished, i.e. the returned set is a minimum. The set is sorted.
+    pub fn cycle_hitting_set(&self, cycles: &[Cycle]) -> (Vec<usize>, bool) {
+        let mut sorted = cycles.iter().collect::<Vec<_>>();
+        sorted.sort_by_key(|cycle| cycle.vertices.len());
+        let mut cycles_of = vec![Vec::new(); self.num_vertices()];
+        for (i, cycle) in sorted.iter().enumerate() {
+            for v in &cycle.vertices {
+                cycles_of[*v].push(i);
+            }
+        }
+        let mut search = HittingSetSearch {
+            hits: vec![0; sorted.len()],
+            cycles: sorted,
+            cycles_of,
+            current: Vec::new(),
+            best: greedy_hitting_set(self.num_vertices(), cycles),
+            budget: SEARCH_BUDGET,
+        };
+        let finished = search.search(0);
+        let mut best = search.best;
+        best.sort();
+        (best, finished)
+    }
+
+    /// Compute a (not necessarily minimum) feedback vertex set of this graph, i.e. a set of
+    /// vertices whose removal makes the graph acyclic, without enumerating its cycles.
+    ///
+    /// The vertex with the highest product of in- and out-degree within a non-trivial
+    /// strongly connected component is removed until no such component remains.
+    pub fn greedy_feedback_vertex_set(&self) -> Vec<usize> {
+        let mut removed = vec![false; self.num_vertices()];
+        let mut result = Vec::new();
+        loop {
+            let graph = self.without_vertices(&removed);
+            let mut best: Option<(usize, usize)> = None;
+            for component in graph.strongly_connected_components() {
+                let v = component[0];
+                let is_cyclic =
+                    component.len() > 1 || graph.successors(v).iter().any(|(u, _)| *u == v);
+                if !is_cyclic {
+                    continue;
+                }
+                let mut in_degree = vec![0; graph.num_vertices()];
+                for &v in &component {
+                    for (u, _) in graph.successors(v) {
+                        in_degree[*u] += 1;
+                    }
+                }
+                for &v in &component {
+                    let score = in_degree[v] * graph.successors(v).len();
+                    if best.map(|(_, s)| score > s).unwrap_or(true) {
+                        best = Some((v, score));
+                    }
+                }
+            }
+            match best {
+                Some((v, _)) => {
+                    removed[v] = true;
+                    result.push(v);
+                }
+                None => break,
+            }
+        }
+        result.sort();
+        result
+    }
+
+    /// **(internal)** A copy of this graph where all edges of the removed vertices
+    /// are deleted.
+    fn without_vertices(&self, removed: &[bool]) -> SignedGraph {
+        let mut graph = SignedGraph::new(self.ids.clone());
+        for v in 0..self.num_vertices() {
+            for (u, sign) in self.successors(v) {
+                if !removed[v] && !removed[*u] {
+                    graph.add_edge(v, *u, *sign);
+                }
+            }
+        }
+        graph
+    }
+}
+
+/// **(internal)** Repeatedly select the vertex which hits the most remaining cycles.
+fn greedy_hitting_set(num_vertices: usize, cycles: &[Cycle]) -> Vec<usize> {
+    let mut hit = vec![false; cycles.len()];
+    let mut result = Vec::new();
+    loop {
+        let mut count = vec![0usize; num_vertices];
+        for (cycle, _) in cycles.iter().zip(&hit).filter(|(_, hit)| !**hit) {
+            for v in &cycle.vertices {
+                count[*v] += 1;
+            }
+        }
+        let best = (0..num_vertices).max_by_key(|v| (count[*v], usize::MAX - *v));
+        match best {
+            Some(v) if count[v] > 0 => {
+                result.push(v);
+                for (i, cycle) in cycles.iter().enumerate() {
+                    if cycle.vertices.contains(&v) {
+                        hit[i] = true;
+                    }
+                }
+            }
+            _ => return result,
+        }
+    }
+}
+
+impl HittingSetSearch<'_> {
+    /// **(internal)** Branch on the vertices of the shortest cycle that is not hit yet by
+    /// the `current` selection. All cycles before `start` are already hit. Returns `false`
+    /// if the search budget was exhausted.
+    fn search(&mut self, start: usize) -> bool {
+        let unhit = (start..self.cycles.len()).find(|i| self.hits[*i] == 0);
+        let examined = unhit.unwrap_or(self.cycles.len()) - start + 1;
+        if self.budget < examined {
+            self.budget = 0;
+            return false;
+        }
+        self.budget -= examined;
+        let index = match unhit {
+            Some(index) => index,
+            None => {
+                if self.current.len() < self.best.len() {
+                    self.best = self.current.clone();
+                }
+                return true;
+            }
+        };
+        if self.current.len() + 1 >= self.best.len() {
+            // Any solution in this branch is at least as large as the best one.
+            return true;
+        }
+        let cycle = self.cycles[index];
+        for v in &cycle.vertices {
+            self.select(*v, true);
+            self.current.push(*v);
+            let finished = self.search(index + 1);
+            self.current.pop();
+            self.select(*v, false);
+            if !finished {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// **(internal)** Add vertex `v` to (or remove it from) the selection.
+    fn select(&mut self, v: usize, is_selected: bool) {
+        for i in &self.cycles_of[v] {
+            if is_selected {
+                self.hits[*i] += 1;
+            } else {
+                self.hits[*i] -= 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph_analysis::{Sign, SignedGraph};
+
+    #[test]
+    fn minimum_feedback_vertex_set() {
+        // Two triangles sharing vertex 2, plus a self-loop on 4.
+        let mut graph = SignedGraph::new(vec![0, 1, 2, 3, 4, 5]);
+        graph.add_edge(0, 1, Sign::Positive);
+        graph.add_edge(1, 2, Sign::Positive);
+        graph.add_edge(2, 0, Sign::Positive);
+        graph.add_edge(2, 3, Sign::Negative);
+        graph.add_edge(3, 5, Sign::Positive);
+        graph.add_edge(5, 2, Sign::Positive);
+        graph.add_edge(4, 4, Sign::Negative);
+
+        let (cycles, complete) = graph.elementary_cycles(usize::MAX, usize::MAX);
+        assert!(complete);
+        assert_eq!((vec![2, 4], true), graph.cycle_hitting_set(&cycles));
+        assert_eq!(vec![2, 4], graph.greedy_feedback_vertex_set());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::graph_analysis::SignedGraph;
+
+impl SignedGraph {
+    /// Compute the strongly connected components of this graph using Tarjan's algorithm.
+    ///
+    /// The components are returned in topological order, i.e. if there is an edge from
+    /// component `A` to component `B`, `A` appears before `B`. Vertices in each component
+    /// are sorted.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let n = self.num_vertices();
+        let mut index = vec![usize::MAX; n];
+        let mut low_link = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut next_index = 0;
+        let mut components = Vec::new();
+
+        for root in 0..n {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            // Explicit DFS stack of (vertex, index of the next successor to explore).
+            let mut dfs = vec![(root, 0usize)];
+            index[root] = next_index;
+            low_link[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some((vertex, next)) = dfs.last_mut() {
+                let vertex = *vertex;
+                if let Some((successor, _)) = self.successors(vertex).get(*next) {
+                    let successor = *successor;
+                    *next += 1;
+                    if index[successor] == usize::MAX {
+                        index[successor] = next_index;
+                        low_link[successor] = next_index;
+                        next_index += 1;
+                        stack.push(successor);
+                        on_stack[successor] = true;
+                        dfs.push((successor, 0));
+                    } else if on_stack[successor] {
+                        low_link[vertex] = low_link[vertex].min(index[successor]);
+                    }
+                } else {
+                    dfs.pop();
+                    if let Some((parent, _)) = dfs.last() {
+                        low_link[*parent] = low_link[*parent].min(low_link[vertex]);
+                    }
+                    if low_link[vertex] == index[vertex] {
+                        let mut component = Vec::new();
+                        while let Some(top) = stack.pop() {
+                            on_stack[top] = false;
+                            component.push(top);
+                            if top == vertex {
+                                break;
+                            }
+                        }
+                        component.sort();
+                        components.push(component);
+                    }
+                }
+            }
+        }
+
+        // Tarjan's algorithm discovers components in reverse topological order.
+        components.reverse();
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph_analysis::{Sign, SignedGraph};
+
+    #[test]
+    fn scc_in_topological_order() {
+        let mut graph = SignedGraph::new(vec![0, 1, 2, 3, 4]);
+        graph.add_edge(3, 4, Sign::Positive);
+        graph.add_edge(4, 3, Sign::Positive);
+        graph.add_edge(4, 0, Sign::Negative);
+        graph.add_edge(0, 1, Sign::Unknown);
+        graph.add_edge(1, 0, Sign::Positive);
+        graph.add_edge(1, 2, Sign::Positive);
+        graph.add_edge(2, 2, Sign::Positive);
+        let components = graph.strongly_connected_components();
+        assert_eq!(vec![vec![3, 4], vec![0, 1], vec![2]], components);
+    }
+}
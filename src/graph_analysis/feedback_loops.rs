@@ -0,0 +1,125 @@
+use crate::boolean_network::BooleanNetworkModel;
+use crate::graph_analysis::{Sign, SignedGraph};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+/// The default limit on the number of enumerated feedback loops.
+pub(crate) const DEFAULT_LOOP_LIMIT: u32 = 10_000;
+
+/// One elementary feedback loop (cycle) of the regulatory graph.
+///
+/// The `variables` are listed in the order of the loop, starting with the variable with
+/// the smallest ID. The `sign` is `unknown` when at least one regulation in the loop
+/// has no declared monotonicity.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FeedbackLoop {
+    variables: Vec<u64>,
+    names: Vec<String>,
+    sign: Sign,
+}
+
+/// The number of positive, negative and unknown feedback loops in which a variable
+/// participates.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LoopParticipation {
+    variable: u64,
+    name: String,
+    positive: usize,
+    negative: usize,
+    unknown: usize,
+}
+
+/// The result of the feedback loop enumeration.
+///
+/// `complete` is false if the enumeration was stopped because the limit on the number of
+/// loops was reached. Variables which are not part of any loop are not listed
+/// in `participation`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FeedbackLoops {
+    loops: Vec<FeedbackLoop>,
+    participation: Vec<LoopParticipation>,
+    complete: bool,
+}
+
+/// Enumerate the feedback loops of the regulatory graph of the given `.aeon` model.
+///
+/// Only loops with at most `max_length` variables are considered (default is unlimited),
+/// and the enumeration stops after `max_count` loops (default is 10 000).
+///
+/// When the model is not valid, a `string` error is thrown.
+#[wasm_bindgen]
+pub fn feedback_loops(
+    model_string: &str,
+    max_length: Option<u32>,
+    max_count: Option<u32>,
+) -> Result<JsValue, String> {
+    let model = BooleanNetworkModel::from_aeon(model_string)?;
+    Ok(model.feedback_loops(max_length, max_count))
+}
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Same as `feedback_loops`, but for the regulatory graph of this model.
+    ///
+    /// Update functions are not considered, hence the result is available even when
+    /// the model is not valid.
+    pub fn feedback_loops(&self, max_length: Option<u32>, max_count: Option<u32>) -> JsValue {
+        let max_length = max_length.map(|it| it as usize).unwrap_or(usize::MAX);
+        let max_count = max_count.unwrap_or(DEFAULT_LOOP_LIMIT) as usize;
+        serde_wasm_bindgen::to_value(&self.compute_feedback_loops(max_length, max_count)).unwrap()
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** Enumerate the feedback loops of this model and count how many loops
+    /// each variable participates in.
+    pub(crate) fn compute_feedback_loops(
+        &self,
+        max_length: usize,
+        max_count: usize,
+    ) -> FeedbackLoops {
+        let graph = SignedGraph::from_model(self);
+        let (cycles, complete) = graph.elementary_cycles(max_length, max_count);
+
+        let mut participation = (0..graph.num_vertices())
+            .map(|v| {
+                let id = graph.id(v);
+                LoopParticipation {
+                    variable: id,
+                    name: self.get_variable_name(id).unwrap(),
+                    positive: 0,
+                    negative: 0,
+                    unknown: 0,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut loops = Vec::new();
+        for cycle in cycles {
+            for v in &cycle.vertices {
+                match cycle.sign {
+                    Sign::Positive => participation[*v].positive += 1,
+                    Sign::Negative => participation[*v].negative += 1,
+                    Sign::Unknown => participation[*v].unknown += 1,
+                }
+            }
+            loops.push(FeedbackLoop {
+                variables: cycle.vertices.iter().map(|v| graph.id(*v)).collect(),
+                names: cycle
+                    .vertices
+                    .iter()
+                    .map(|v| participation[*v].name.clone())
+                    .collect(),
+                sign: cycle.sign,
+            });
+        }
+        participation.retain(|p| p.positive + p.negative + p.unknown > 0);
+
+        FeedbackLoops {
+            loops,
+            participation,
+            complete,
+        }
+    }
+}
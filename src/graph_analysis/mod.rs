@@ -0,0 +1,97 @@
+//! Algorithms that work with the topology of the regulatory graph of a model.
+//!
+//! All algorithms operate on a `SignedGraph`, which is a simple indexed representation
+//! of the regulatory graph of a `BooleanNetworkModel`.
+
+use crate::boolean_network::BooleanNetworkModel;
+use serde::{Deserialize, Serialize};
+
+pub mod algo_cycles;
+pub mod algo_feedback_vertex_set;
+pub mod algo_scc;
+pub mod feedback_loops;
+pub mod structure;
+
+/// The sign of a regulation (or of a path or cycle in the regulatory graph).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sign {
+    Positive,
+    Negative,
+    Unknown,
+}
+
+/// A directed graph with signed edges. Vertices are indexed `0..n`, and each vertex
+/// corresponds to one variable ID of the original model (ordered by ID).
+#[derive(Clone, Debug)]
+pub struct SignedGraph {
+    ids: Vec<u64>,
+    successors: Vec<Vec<(usize, Sign)>>,
+}
+
+impl Sign {
+    /// Convert a monotonicity string (`"+"`, `"-"` or `""`) to a sign.
+    pub fn from_monotonicity(monotonicity: &str) -> Sign {
+        match monotonicity {
+            "+" => Sign::Positive,
+            "-" => Sign::Negative,
+            _ => Sign::Unknown,
+        }
+    }
+
+    /// The sign of a path consisting of two parts with signs `self` and `other`.
+    pub fn compose(self, other: Sign) -> Sign {
+        match (self, other) {
+            (Sign::Unknown, _) | (_, Sign::Unknown) => Sign::Unknown,
+            (a, b) if a == b => Sign::Positive,
+            _ => Sign::Negative,
+        }
+    }
+}
+
+impl SignedGraph {
+    /// Create a graph with vertices for the given variable IDs and no edges.
+    pub fn new(ids: Vec<u64>) -> SignedGraph {
+        SignedGraph {
+            successors: vec![Vec::new(); ids.len()],
+            ids,
+        }
+    }
+
+    /// Build the regulatory graph of the given model.
+    pub fn from_model(model: &BooleanNetworkModel) -> SignedGraph {
+        let variables = model.sorted_variables();
+        let mut graph = SignedGraph::new(variables.iter().map(|var| var.id).collect());
+        for reg in model.sorted_regulations() {
+            let source = graph.index_of(reg.source).unwrap();
+            let target = graph.index_of(reg.target).unwrap();
+            graph.add_edge(source, target, Sign::from_monotonicity(&reg.monotonicity));
+        }
+        graph
+    }
+
+    /// Add a new edge to the graph.
+    pub fn add_edge(&mut self, source: usize, target: usize, sign: Sign) {
+        self.successors[source].push((target, sign));
+    }
+
+    /// The number of vertices of this graph.
+    pub fn num_vertices(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// The variable ID of the given vertex.
+    pub fn id(&self, vertex: usize) -> u64 {
+        self.ids[vertex]
+    }
+
+    /// The vertex index of the given variable ID.
+    pub fn index_of(&self, id: u64) -> Option<usize> {
+        self.ids.binary_search(&id).ok()
+    }
+
+    /// The outgoing edges of the given vertex.
+    pub fn successors(&self, vertex: usize) -> &[(usize, Sign)] {
+        &self.successors[vertex]
+    }
+}
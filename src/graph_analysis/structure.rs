@@ -0,0 +1,117 @@
+use crate::boolean_network::BooleanNetworkModel;
+use crate::graph_analysis::algo_cycles::Cycle;
+use crate::graph_analysis::feedback_loops::DEFAULT_LOOP_LIMIT;
+use crate::graph_analysis::{Sign, SignedGraph};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+/// A set of variables of the regulatory graph.
+///
+/// For feedback vertex sets, `minimum` is true if the set is guaranteed to be the smallest
+/// possible. Otherwise, it is only a (heuristically small) upper bound.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VariableSet {
+    variables: Vec<u64>,
+    names: Vec<String>,
+    minimum: bool,
+}
+
+/// The structural decomposition of a regulatory graph.
+///
+///  - `components` are the strongly connected components in topological order (regulators
+///    of a component always appear before the component itself).
+///  - `positive_feedback_vertex_set` intersects every positive feedback loop, and
+///    `negative_feedback_vertex_set` every negative feedback loop. Loops with unknown sign
+///    are treated as both positive and negative.
+///  - `fixed_point_bound` is the upper bound on the number of fixed points, which
+///    is `2^k` for a positive feedback vertex set of size `k`.
+///  - `only_fixed_points` is true when there is no negative feedback loop, meaning that
+///    every attractor of the network is a fixed point.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GraphStructure {
+    components: Vec<VariableSet>,
+    positive_feedback_vertex_set: VariableSet,
+    negative_feedback_vertex_set: VariableSet,
+    fixed_point_bound: f64,
+    only_fixed_points: bool,
+}
+
+/// Compute the strongly connected components and feedback vertex sets of the regulatory
+/// graph of the given `.aeon` model. See `GraphStructure` for details.
+///
+/// When the model is not valid, a `string` error is thrown.
+#[wasm_bindgen]
+pub fn regulatory_graph_structure(model_string: &str) -> Result<JsValue, String> {
+    let model = BooleanNetworkModel::from_aeon(model_string)?;
+    Ok(model.regulatory_graph_structure())
+}
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Same as `regulatory_graph_structure`, but for the regulatory graph of this model.
+    pub fn regulatory_graph_structure(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.compute_graph_structure()).unwrap()
+    }
+}
+
+impl BooleanNetworkModel {
+    /// **(internal)** Compute the `GraphStructure` of this model.
+    pub(crate) fn compute_graph_structure(&self) -> GraphStructure {
+        let graph = SignedGraph::from_model(self);
+        let (cycles, complete) = graph.elementary_cycles(usize::MAX, DEFAULT_LOOP_LIMIT as usize);
+
+        let components = graph
+            .strongly_connected_components()
+            .into_iter()
+            .map(|component| self.variable_set(&graph, component, true))
+            .collect::<Vec<_>>();
+
+        let feedback_vertex_set = |sign: Sign| {
+            if complete {
+                let signed = cycles
+                    .iter()
+                    .filter(|cycle| cycle.sign == sign || cycle.sign == Sign::Unknown)
+                    .cloned()
+                    .collect::<Vec<Cycle>>();
+                let (set, minimum) = graph.cycle_hitting_set(&signed);
+                self.variable_set(&graph, set, minimum)
+            } else {
+                // Too many cycles: any feedback vertex set of the whole graph is also
+                // a signed feedback vertex set.
+                self.variable_set(&graph, graph.greedy_feedback_vertex_set(), false)
+            }
+        };
+        let positive = feedback_vertex_set(Sign::Positive);
+        let negative = feedback_vertex_set(Sign::Negative);
+
+        GraphStructure {
+            components,
+            fixed_point_bound: 2.0f64.powi(positive.variables.len() as i32),
+            only_fixed_points: negative.variables.is_empty(),
+            positive_feedback_vertex_set: positive,
+            negative_feedback_vertex_set: negative,
+        }
+    }
+
+    /// **(internal)** Convert a list of graph vertices to a `VariableSet`.
+    fn variable_set(
+        &self,
+        graph: &SignedGraph,
+        vertices: Vec<usize>,
+        minimum: bool,
+    ) -> VariableSet {
+        let variables = vertices
+            .into_iter()
+            .map(|v| graph.id(v))
+            .collect::<Vec<_>>();
+        VariableSet {
+            names: variables
+                .iter()
+                .map(|id| self.get_variable_name(*id).unwrap())
+                .collect(),
+            variables,
+            minimum,
+        }
+    }
+}
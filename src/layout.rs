@@ -0,0 +1,204 @@
+use crate::graph_analysis::SignedGraph;
+use std::f64::consts::PI;
+
+/// The ideal distance between two adjacent variables in the computed layout.
+const NODE_DISTANCE: f64 = 100.0;
+/// The number of iterations of the force-directed algorithm.
+const ITERATIONS: usize = 300;
+
+/// Compute a force-directed (Fruchterman-Reingold) layout of the given graph.
+///
+/// Vertices with a `Some` position are fixed and only affect the placement of the
+/// remaining (free) vertices. In `hierarchical` mode, the vertical position of every free
+/// vertex is given by the topological order of its strongly connected component, and
+/// the forces only affect the horizontal position.
+///
+/// The algorithm is deterministic: the same input always yields the same layout.
+pub fn force_directed_layout(
+    graph: &SignedGraph,
+    fixed: &[Option<(f64, f64)>],
+    hierarchical: bool,
+) -> Vec<(f64, f64)> {
+    let n = graph.num_vertices();
+    let free: Vec<usize> = (0..n).filter(|v| fixed[*v].is_none()).collect();
+    let mut positions = initial_positions(graph, fixed, hierarchical);
+    if free.is_empty() {
+        return positions;
+    }
+
+    // Forces are symmetric, so we use an undirected view of the graph without self-loops.
+    let mut neighbours = vec![Vec::new(); n];
+    for v in 0..n {
+        for (u, _) in graph.successors(v) {
+            if *u != v {
+                neighbours[v].push(*u);
+                neighbours[*u].push(v);
+            }
+        }
+    }
+
+    let k = NODE_DISTANCE;
+    let initial_temperature = k * (free.len() as f64).sqrt();
+    for iteration in 0..ITERATIONS {
+        let temperature = initial_temperature * (1.0 - iteration as f64 / ITERATIONS as f64);
+        let mut displacement = vec![(0.0, 0.0); n];
+        for &v in &free {
+            for u in 0..n {
+                if u == v {
+                    continue;
+                }
+                let (dx, dy, distance) = difference(positions[v], positions[u], v, u);
+                let force = k * k / distance;
+                displacement[v].0 += dx / distance * force;
+                displacement[v].1 += dy / distance * force;
+            }
+            for &u in &neighbours[v] {
+                let (dx, dy, distance) = difference(positions[v], positions[u], v, u);
+                let force = distance * distance / k;
+                displacement[v].0 -= dx / distance * force;
+                displacement[v].1 -= dy / distance * force;
+            }
+        }
+        for &v in &free {
+            let (dx, dy) = displacement[v];
+            let (dx, dy) = if hierarchical { (dx, 0.0) } else { (dx, dy) };
+            let length = (dx * dx + dy * dy).sqrt();
+            if length > 0.0 {
+                let step = length.min(temperature);
+                positions[v].0 += dx / length * step;
+                positions[v].1 += dy / length * step;
+            }
+        }
+    }
+
+    positions
+}
+
+/// **(internal)** Place the free vertices on a circle around the fixed vertices, or into
+/// layers based on the strongly connected components in the `hierarchical` mode.
+fn initial_positions(
+    graph: &SignedGraph,
+    fixed: &[Option<(f64, f64)>],
+    hierarchical: bool,
+) -> Vec<(f64, f64)> {
+    let n = graph.num_vertices();
+    let fixed_positions: Vec<(f64, f64)> = fixed.iter().flatten().cloned().collect();
+    let center = if fixed_positions.is_empty() {
+        (0.0, 0.0)
+    } else {
+        let count = fixed_positions.len() as f64;
+        let x = fixed_positions.iter().map(|p| p.0).sum::<f64>() / count;
+        let y = fixed_positions.iter().map(|p| p.1).sum::<f64>() / count;
+        (x, y)
+    };
+
+    let mut positions = vec![center; n];
+    if hierarchical {
+        let top = fixed_positions
+            .iter()
+            .map(|p| p.1)
+            .fold(f64::INFINITY, f64::min);
+        let top = if top.is_finite() { top } else { 0.0 };
+        let layers = component_layers(graph);
+        let mut layer_size = vec![0usize; n];
+        for v in 0..n {
+            let layer = layers[v];
+            positions[v] = (
+                center.0 + layer_size[layer] as f64 * NODE_DISTANCE,
+                top + layer as f64 * NODE_DISTANCE,
+            );
+            layer_size[layer] += 1;
+        }
+    } else {
+        let free_count = fixed.iter().filter(|p| p.is_none()).count();
+        let radius = NODE_DISTANCE * (1.0 + free_count as f64).sqrt();
+        let mut i = 0;
+        for v in 0..n {
+            if fixed[v].is_none() {
+                let angle = 2.0 * PI * i as f64 / free_count as f64;
+                positions[v] = (
+                    center.0 + radius * angle.cos(),
+                    center.1 + radius * angle.sin(),
+                );
+                i += 1;
+            }
+        }
+    }
+
+    for v in 0..n {
+        if let Some(position) = fixed[v] {
+            positions[v] = position;
+        }
+    }
+    positions
+}
+
+/// **(internal)** Assign each vertex a layer: the length of the longest path leading to its
+/// strongly connected component in the graph of components.
+fn component_layers(graph: &SignedGraph) -> Vec<usize> {
+    let components = graph.strongly_connected_components();
+    let mut component_of = vec![0; graph.num_vertices()];
+    for (i, component) in components.iter().enumerate() {
+        for v in component {
+            component_of[*v] = i;
+        }
+    }
+    // Components are in topological order, so every predecessor is processed first.
+    let mut layers = vec![0; graph.num_vertices()];
+    let mut component_layer = vec![0; components.len()];
+    for (i, component) in components.iter().enumerate() {
+        for v in component {
+            layers[*v] = component_layer[i];
+            for (u, _) in graph.successors(*v) {
+                let target = component_of[*u];
+                if target != i {
+                    component_layer[target] = component_layer[target].max(component_layer[i] + 1);
+                }
+            }
+        }
+    }
+    layers
+}
+
+/// **(internal)** Compute the difference vector between two positions and its length. If
+/// the two positions coincide, a small deterministic offset based on the vertex indices
+/// is used instead.
+fn difference(a: (f64, f64), b: (f64, f64), a_index: usize, b_index: usize) -> (f64, f64, f64) {
+    let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+    let distance = (dx * dx + dy * dy).sqrt();
+    if distance > 0.01 {
+        (dx, dy, distance)
+    } else {
+        let angle = (a_index * 31 + b_index * 17) as f64;
+        (0.01 * angle.cos(), 0.01 * angle.sin(), 0.01)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph_analysis::{Sign, SignedGraph};
+    use crate::layout::{component_layers, force_directed_layout};
+
+    #[test]
+    fn deterministic_hierarchical_layout() {
+        // 0 -> {1, 2} -> 3, where 1 and 2 form a cycle.
+        let mut graph = SignedGraph::new(vec![0, 1, 2, 3]);
+        graph.add_edge(0, 1, Sign::Positive);
+        graph.add_edge(1, 2, Sign::Positive);
+        graph.add_edge(2, 1, Sign::Negative);
+        graph.add_edge(2, 3, Sign::Positive);
+        assert_eq!(vec![0, 1, 1, 2], component_layers(&graph));
+
+        let free = vec![None; 4];
+        let layout = force_directed_layout(&graph, &free, true);
+        assert_eq!(layout, force_directed_layout(&graph, &free, true));
+        let rows = layout.iter().map(|p| p.1).collect::<Vec<_>>();
+        assert_eq!(vec![0.0, 100.0, 100.0, 200.0], rows);
+
+        let fixed = vec![Some((5.0, 5.0)), None, None, None];
+        let layout = force_directed_layout(&graph, &fixed, false);
+        assert_eq!(layout, force_directed_layout(&graph, &fixed, false));
+        assert_eq!((5.0, 5.0), layout[0]);
+        assert!(layout.iter().all(|p| p.0.is_finite() && p.1.is_finite()));
+    }
+}
@@ -8,10 +8,12 @@ use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
 pub use conversions::read_layout;
-pub use conversions::Conversions;
+pub use conversions::{ConversionError, ConversionErrorKind, Conversions};
 
 mod bdt;
+mod graph_analysis;
 mod graph_task_context;
+mod layout;
 mod scc;
 mod util;
 mod utils;
@@ -20,6 +22,7 @@ mod boolean_network;
 mod computation;
 mod conversions;
 mod decision_tree;
+mod static_constraints;
 
 #[derive(Serialize, Deserialize)]
 pub struct CardinalityData {
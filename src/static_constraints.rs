@@ -0,0 +1,347 @@
+//! Static constraint analysis of regulations (observability and monotonicity).
+//!
+//! The symbolic encoding of the constraints is the same as in the static constraint
+//! analysis in `lib-param-bn`. For more context, go there.
+
+use crate::boolean_network::{BooleanNetworkModel, ModelProblem};
+use biodivine_lib_bdd::Bdd;
+use biodivine_lib_param_bn::symbolic_async_graph::{SymbolicAsyncGraph, SymbolicContext};
+use biodivine_lib_param_bn::{BooleanNetwork, Monotonicity, RegulatoryGraph, VariableId};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+/// Describes for how many parametrisations of a network a static constraint holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConstraintStatus {
+    All,
+    Some,
+    None,
+}
+
+/// The result of the static constraint check for one regulation.
+///
+/// The `observable`, `activation` and `inhibition` fields state whether the corresponding
+/// property holds for all, some, or no parametrisations of the network (ignoring all
+/// declared annotations). `violated` is true if the declared annotation holds for no
+/// parametrisation. `suggested_observable` and `suggested_monotonicity` are set when
+/// the declared annotation can be tightened, because the stronger property holds for all
+/// parametrisations.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RegulationConstraints {
+    source: String,
+    target: String,
+    declared_observable: bool,
+    declared_monotonicity: String,
+    observable: ConstraintStatus,
+    activation: ConstraintStatus,
+    inhibition: ConstraintStatus,
+    violated: bool,
+    suggested_observable: Option<bool>,
+    suggested_monotonicity: Option<String>,
+}
+
+/// Check the declared monotonicity and observability of every regulation in the given
+/// `.aeon` model against its update functions.
+///
+/// The result is a list of objects (one per regulation) which describe whether each
+/// property holds for all, some, or no parametrisations, and which annotations could
+/// be tightened.
+///
+/// When the model is not valid, a `string` error is thrown.
+#[wasm_bindgen]
+pub fn check_static_constraints(model_string: &str) -> Result<JsValue, String> {
+    let network = BooleanNetwork::try_from(model_string)?;
+    let result = regulation_constraints(&network)?;
+    Ok(serde_wasm_bindgen::to_value(&result).unwrap())
+}
+
+#[wasm_bindgen]
+impl BooleanNetworkModel {
+    /// Same as `check_static_constraints`, but for this model.
+    ///
+    /// When the model is not valid, the error is a list of problems, the same as the one
+    /// returned by `get_problems`.
+    pub fn check_static_constraints(&self) -> Result<JsValue, JsValue> {
+        let network = self
+            .to_boolean_network()
+            .map_err(|problems| ModelProblem::list_to_js(&problems))?;
+        let result = regulation_constraints(&network)
+            .map_err(|message| ModelProblem::list_to_js(&[ModelProblem::global(message)]))?;
+        Ok(serde_wasm_bindgen::to_value(&result).unwrap())
+    }
+}
+
+/// Compute `RegulationConstraints` for all regulations of the given network.
+pub fn regulation_constraints(
+    network: &BooleanNetwork,
+) -> Result<Vec<RegulationConstraints>, String> {
+    let relaxed = relax_regulations(network)?;
+    let graph = SymbolicAsyncGraph::new(&relaxed)?;
+    let context = graph.symbolic_context();
+    let unit = graph.unit_colors().as_bdd();
+    let status = |bdd: &Bdd| {
+        let bdd = bdd.and(unit);
+        if bdd.is_false() {
+            ConstraintStatus::None
+        } else if &bdd == unit {
+            ConstraintStatus::All
+        } else {
+            ConstraintStatus::Some
+        }
+    };
+
+    let mut result = Vec::new();
+    for reg in network.as_graph().regulations() {
+        let fn_is_true = mk_update_function_is_true(&relaxed, context, reg.get_target());
+        let regulator = reg.get_regulator();
+        let observable = status(&mk_observability(context, &fn_is_true, regulator));
+        let activation = status(&mk_non_activation(context, &fn_is_true, regulator).not());
+        let inhibition = status(&mk_non_inhibition(context, &fn_is_true, regulator).not());
+
+        let declared_monotonicity = match reg.get_monotonicity() {
+            None => String::new(),
+            Some(Monotonicity::Activation) => "+".to_string(),
+            Some(Monotonicity::Inhibition) => "-".to_string(),
+        };
+        let violated = (reg.is_observable() && observable == ConstraintStatus::None)
+            || (declared_monotonicity == "+" && activation == ConstraintStatus::None)
+            || (declared_monotonicity == "-" && inhibition == ConstraintStatus::None);
+        let suggested_observable = if !reg.is_observable() && observable == ConstraintStatus::All {
+            Some(true)
+        } else {
+            None
+        };
+        // If both hold for all parametrisations, the regulation is never observable and
+        // no monotonicity is more appropriate than the other.
+        let suggested_monotonicity = match (declared_monotonicity.as_str(), activation, inhibition)
+        {
+            ("", ConstraintStatus::All, ConstraintStatus::All) => None,
+            ("", ConstraintStatus::All, _) => Some("+".to_string()),
+            ("", _, ConstraintStatus::All) => Some("-".to_string()),
+            _ => None,
+        };
+
+        result.push(RegulationConstraints {
+            source: network.get_variable_name(reg.get_regulator()).clone(),
+            target: network.get_variable_name(reg.get_target()).clone(),
+            declared_observable: reg.is_observable(),
+            declared_monotonicity,
+            observable,
+            activation,
+            inhibition,
+            violated,
+            suggested_observable,
+            suggested_monotonicity,
+        });
+    }
+    Ok(result)
+}
+
+/// Create a copy of the given network where all regulations are non-observable and have
+/// no monotonicity, such that the network admits every parametrisation of its functions.
+pub fn relax_regulations(network: &BooleanNetwork) -> Result<BooleanNetwork, String> {
+    let names = network
+        .variables()
+        .map(|v| network.get_variable_name(v).clone())
+        .collect::<Vec<_>>();
+    let mut graph = RegulatoryGraph::new(names);
+    for reg in network.as_graph().regulations() {
+        graph.add_regulation(
+            network.get_variable_name(reg.get_regulator()),
+            network.get_variable_name(reg.get_target()),
+            false,
+            None,
+        )?;
+    }
+    with_regulatory_graph(network, graph)
+}
+
+/// Create a copy of the given network where the annotations of all regulations are
+/// tightened according to the `suggested_observable` and `suggested_monotonicity`
+/// of `regulation_constraints`.
+///
+/// This is useful for formats which do not declare monotonicity or observability at all.
+pub fn tighten_regulations(network: &BooleanNetwork) -> Result<BooleanNetwork, String> {
+    let constraints = regulation_constraints(network)?;
+    let names = network
+        .variables()
+        .map(|v| network.get_variable_name(v).clone())
+        .collect::<Vec<_>>();
+    let mut graph = RegulatoryGraph::new(names);
+    for (reg, constraint) in network.as_graph().regulations().zip(constraints) {
+        let monotonicity = match reg.get_monotonicity() {
+            Some(monotonicity) => Some(monotonicity),
+            None => match constraint.suggested_monotonicity.as_deref() {
+                Some("+") => Some(Monotonicity::Activation),
+                Some("-") => Some(Monotonicity::Inhibition),
+                _ => None,
+            },
+        };
+        graph.add_regulation(
+            network.get_variable_name(reg.get_regulator()),
+            network.get_variable_name(reg.get_target()),
+            reg.is_observable() || constraint.suggested_observable == Some(true),
+            monotonicity,
+        )?;
+    }
+    with_regulatory_graph(network, graph)
+}
+
+/// **(internal)** Create a network with the given regulatory `graph` and the parameters and
+/// update functions of the given `network`.
+fn with_regulatory_graph(
+    network: &BooleanNetwork,
+    graph: RegulatoryGraph,
+) -> Result<BooleanNetwork, String> {
+    let mut result = BooleanNetwork::new(graph);
+    for p in network.parameters() {
+        let parameter = network.get_parameter(p);
+        result.add_parameter(parameter.get_name(), parameter.get_arity())?;
+    }
+    for v in network.variables() {
+        if let Some(function) = network.get_update_function(v) {
+            let function = function.to_string(network);
+            result.add_string_update_function(network.get_variable_name(v), &function)?;
+        }
+    }
+    Ok(result)
+}
+
+/// A `Bdd` which is true for all states and parametrisations where the update function of
+/// the `target` variable is true.
+pub fn mk_update_function_is_true(
+    network: &BooleanNetwork,
+    context: &SymbolicContext,
+    target: VariableId,
+) -> Bdd {
+    if let Some(function) = network.get_update_function(target) {
+        context.mk_fn_update_true(function)
+    } else {
+        context.mk_implicit_function_is_true(target, &network.regulators(target))
+    }
+}
+
+/// A `Bdd` of parametrisations where the update function (given as `fn_is_true`) depends
+/// on the `regulator` in at least one state.
+pub fn mk_observability(context: &SymbolicContext, fn_is_true: &Bdd, regulator: VariableId) -> Bdd {
+    let regulator_var = context.state_variables()[regulator.to_index()];
+    let regulator_is_true = context.mk_state_variable_is_true(regulator);
+    let regulator_is_false = regulator_is_true.not();
+    let fn_x1_to_1 = fn_is_true.and(&regulator_is_true).var_exists(regulator_var);
+    let fn_x0_to_1 = fn_is_true
+        .and(&regulator_is_false)
+        .var_exists(regulator_var);
+    fn_x1_to_1
+        .xor(&fn_x0_to_1)
+        .exists(context.state_variables())
+}
+
+/// A `Bdd` of parametrisations where the update function (given as `fn_is_true`) decreases
+/// in at least one state when the `regulator` increases, i.e. the regulation is not
+/// an activation.
+pub fn mk_non_activation(
+    context: &SymbolicContext,
+    fn_is_true: &Bdd,
+    regulator: VariableId,
+) -> Bdd {
+    let regulator_var = context.state_variables()[regulator.to_index()];
+    let regulator_is_true = context.mk_state_variable_is_true(regulator);
+    let regulator_is_false = regulator_is_true.not();
+    let fn_is_false = fn_is_true.not();
+    let fn_x1_to_0 = fn_is_false
+        .and(&regulator_is_true)
+        .var_exists(regulator_var);
+    let fn_x0_to_1 = fn_is_true
+        .and(&regulator_is_false)
+        .var_exists(regulator_var);
+    fn_x0_to_1
+        .and(&fn_x1_to_0)
+        .exists(context.state_variables())
+}
+
+/// A `Bdd` of parametrisations where the update function (given as `fn_is_true`) increases
+/// in at least one state when the `regulator` increases, i.e. the regulation is not
+/// an inhibition.
+pub fn mk_non_inhibition(
+    context: &SymbolicContext,
+    fn_is_true: &Bdd,
+    regulator: VariableId,
+) -> Bdd {
+    let regulator_var = context.state_variables()[regulator.to_index()];
+    let regulator_is_true = context.mk_state_variable_is_true(regulator);
+    let regulator_is_false = regulator_is_true.not();
+    let fn_is_false = fn_is_true.not();
+    let fn_x0_to_0 = fn_is_false
+        .and(&regulator_is_false)
+        .var_exists(regulator_var);
+    let fn_x1_to_1 = fn_is_true.and(&regulator_is_true).var_exists(regulator_var);
+    fn_x0_to_0
+        .and(&fn_x1_to_1)
+        .exists(context.state_variables())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::static_constraints::ConstraintStatus::{All, None, Some};
+    use crate::static_constraints::{regulation_constraints, tighten_regulations};
+    use biodivine_lib_param_bn::BooleanNetwork;
+
+    #[test]
+    fn non_monotone_and_unobservable_regulations() {
+        let aeon =
+            "a -> b\nc -| b\nd ->? b\n$b: a ^ c\n$a: a\n$c: c\n$d: d\na -> a\nc -> c\nd -> d\n";
+        let network = BooleanNetwork::try_from(aeon).unwrap();
+        let constraints = regulation_constraints(&network).unwrap();
+        let find = |source: &str, target: &str| {
+            constraints
+                .iter()
+                .find(|c| c.source == source && c.target == target)
+                .unwrap()
+        };
+
+        // `b` depends on `a` and `c`, but neither regulation is monotone.
+        let (a, c) = (find("a", "b"), find("c", "b"));
+        assert_eq!(
+            (All, None, None),
+            (a.observable, a.activation, a.inhibition)
+        );
+        assert_eq!(
+            (All, None, None),
+            (c.observable, c.activation, c.inhibition)
+        );
+        assert!(a.violated && c.violated);
+        assert_eq!(Option::None, a.suggested_monotonicity);
+
+        // `b` does not depend on `d`, so both monotonicity constraints hold trivially.
+        let d = find("d", "b");
+        assert_eq!((None, All, All), (d.observable, d.activation, d.inhibition));
+        assert!(!d.violated);
+        assert_eq!(
+            (Option::None, Option::None),
+            (d.suggested_observable, d.suggested_monotonicity.clone())
+        );
+
+        // Implicit functions admit some (but not all) parametrisations of each property.
+        let network = BooleanNetwork::try_from("a -? b\n").unwrap();
+        let constraints = regulation_constraints(&network).unwrap();
+        let a = &constraints[0];
+        assert_eq!(
+            (Some, Some, Some),
+            (a.observable, a.activation, a.inhibition)
+        );
+        assert!(!a.violated);
+    }
+
+    #[test]
+    fn tighten_declared_regulations() {
+        let aeon = "a -?? b\nc -?? b\n$b: a & !c\n$a: true\n$c: true\n";
+        let network = BooleanNetwork::try_from(aeon).unwrap();
+        let tightened = tighten_regulations(&network).unwrap();
+        assert_eq!(
+            "a -> b\nc -| b\n$a: true\n$b: a & !c\n$c: true\n",
+            tightened.to_string()
+        );
+    }
+}